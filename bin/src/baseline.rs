@@ -0,0 +1,368 @@
+//! Warnings recorded in `.hemtt/baseline.toml`, that are hidden from later runs
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use hemtt_common::reporting::{Code, WorkspaceFiles};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Error,
+    report::{Report, WithIncludes},
+};
+
+const FILE: &str = "baseline.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A warning recorded in the baseline
+pub struct Entry {
+    /// The code of the warning
+    code: String,
+    /// The file the warning was reported in
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    file: String,
+    /// A fingerprint of the warning, that does not change when lines are added above it
+    fingerprint: String,
+}
+
+impl Entry {
+    fn of(code: &dyn Code, files: &WorkspaceFiles) -> Option<Self> {
+        let diagnostic = code.diagnostic()?;
+        Some(Self {
+            code: code.ident().to_string(),
+            file: diagnostic
+                .primary_label()
+                .map(|label| label.file().as_str().trim_start_matches('/').to_string())
+                .unwrap_or_default(),
+            fingerprint: diagnostic.fingerprint(files),
+        })
+    }
+
+    #[must_use]
+    /// The code of the warning
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    #[must_use]
+    /// The file the warning was reported in
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct File {
+    #[serde(default, rename = "warning")]
+    warnings: Vec<Entry>,
+}
+
+#[derive(Debug, Default)]
+/// Warnings that were present when the baseline was written
+///
+/// Every warning that passes through the baseline is recorded, so a new baseline can be written
+pub struct Baseline {
+    /// Baselined warnings that have not been reported yet, by fingerprint
+    remaining: Mutex<HashMap<String, Vec<Entry>>>,
+    /// Every warning that was reported, including baselined warnings
+    recorded: Mutex<Vec<Entry>>,
+}
+
+impl Baseline {
+    /// Read `.hemtt/baseline.toml`, or an empty baseline if it does not exist
+    ///
+    /// # Errors
+    /// [`Error::Io`] if the file can not be read
+    /// [`Error::TomlDe`] if the file is not valid
+    pub fn load(hemtt_folder: &Path) -> Result<Self, Error> {
+        let path = hemtt_folder.join(FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file: File = toml::from_str(&std::fs::read_to_string(path)?)?;
+        let mut remaining: HashMap<String, Vec<Entry>> = HashMap::new();
+        for entry in file.warnings {
+            remaining
+                .entry(entry.fingerprint.clone())
+                .or_default()
+                .push(entry);
+        }
+        Ok(Self {
+            remaining: Mutex::new(remaining),
+            recorded: Mutex::default(),
+        })
+    }
+
+    #[must_use]
+    /// Removes the warnings that are in the baseline from a report
+    ///
+    /// Each entry hides one warning, so a warning that is reported more often than when
+    /// the baseline was written is still reported
+    ///
+    /// # Panics
+    /// If a lock is poisoned
+    pub fn filter(&self, report: &Report) -> Report {
+        let files = WorkspaceFiles::new();
+        let warnings = report
+            .warnings(WithIncludes::Yes)
+            .into_iter()
+            .map(|warning| {
+                let entry = Entry::of(warning.as_ref(), &files);
+                (warning, entry)
+            })
+            .collect::<Vec<_>>();
+        let mut filtered = Report::new();
+        filtered.add_errors(report.errors(WithIncludes::Yes));
+        {
+            let mut remaining = self.remaining.lock().unwrap();
+            for (warning, entry) in &warnings {
+                let baselined = entry.as_ref().is_some_and(|entry| {
+                    remaining
+                        .get_mut(&entry.fingerprint)
+                        .and_then(Vec::pop)
+                        .is_some()
+                });
+                if !baselined {
+                    filtered.warn(warning.clone());
+                }
+            }
+        }
+        self.recorded
+            .lock()
+            .unwrap()
+            .extend(warnings.into_iter().filter_map(|(_, entry)| entry));
+        filtered
+    }
+
+    #[must_use]
+    /// Baselined warnings that were not reported
+    ///
+    /// # Panics
+    /// If the lock is poisoned
+    pub fn unmatched(&self) -> Vec<Entry> {
+        let mut unmatched = self
+            .remaining
+            .lock()
+            .unwrap()
+            .values()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        unmatched.sort_by(|a, b| (&a.file, &a.code).cmp(&(&b.file, &b.code)));
+        unmatched
+    }
+
+    /// Write every warning that was reported to `.hemtt/baseline.toml`
+    ///
+    /// Returns the path and the number of warnings written
+    ///
+    /// # Errors
+    /// [`Error::Io`] if the file can not be written
+    /// [`Error::TomlSer`] if the baseline can not be serialized
+    ///
+    /// # Panics
+    /// If the lock is poisoned
+    pub fn write(&self, hemtt_folder: &Path) -> Result<(PathBuf, usize), Error> {
+        let mut warnings = self.recorded.lock().unwrap().clone();
+        warnings.sort_by(|a, b| {
+            (&a.file, &a.code, &a.fingerprint).cmp(&(&b.file, &b.code, &b.fingerprint))
+        });
+        let path = hemtt_folder.join(FILE);
+        let count = warnings.len();
+        std::fs::write(
+            &path,
+            format!(
+                "# Warnings that are not reported, written by `hemtt check --write-baseline`\n\n{}",
+                toml::to_string(&File { warnings })?
+            ),
+        )?;
+        Ok((path, count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ops::Range, sync::Arc};
+
+    use hemtt_common::{
+        reporting::{Code, Diagnostic, Label, Severity},
+        workspace::{Workspace, WorkspacePath},
+    };
+
+    use crate::report::{Report, WithIncludes};
+
+    use super::Baseline;
+
+    struct Warning {
+        ident: &'static str,
+        path: WorkspacePath,
+        span: Range<usize>,
+        /// A secondary label in another file, before the primary label
+        related: Option<WorkspacePath>,
+    }
+
+    impl Code for Warning {
+        fn ident(&self) -> &'static str {
+            self.ident
+        }
+
+        fn message(&self) -> String {
+            String::from("something is wrong")
+        }
+
+        fn severity(&self) -> Severity {
+            Severity::Warning
+        }
+
+        fn diagnostic(&self) -> Option<Diagnostic> {
+            let mut diagnostic = Diagnostic::simple(self);
+            if let Some(related) = &self.related {
+                diagnostic = diagnostic.with_label(Label::secondary(related.clone(), 0..1));
+            }
+            Some(diagnostic.with_label(Label::primary(self.path.clone(), self.span.clone())))
+        }
+    }
+
+    fn source(workspace: &WorkspacePath, path: &str, content: &str) -> WorkspacePath {
+        let path = workspace.join(path).unwrap();
+        path.create_file()
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+        path
+    }
+
+    fn warning(ident: &'static str, path: &WorkspacePath, span: Range<usize>) -> Arc<dyn Code> {
+        Arc::new(Warning {
+            ident,
+            path: path.clone(),
+            span,
+            related: None,
+        })
+    }
+
+    fn idents(report: &Report) -> Vec<&'static str> {
+        report
+            .warnings(WithIncludes::Yes)
+            .iter()
+            .map(|warning| warning.ident())
+            .collect()
+    }
+
+    /// A baseline of the warnings in a report, written to and read from a folder
+    fn baseline(name: &str, report: &Report) -> Baseline {
+        let folder = std::env::temp_dir().join(format!("hemtt-test-baseline-{name}"));
+        std::fs::create_dir_all(&folder).unwrap();
+        let recording = Baseline::default();
+        let _ = recording.filter(report);
+        recording.write(&folder).unwrap();
+        let baseline = Baseline::load(&folder).unwrap();
+        std::fs::remove_dir_all(&folder).unwrap();
+        baseline
+    }
+
+    #[test]
+    fn filter() {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let file = source(&workspace, "test.sqf", "private _a = 1;\nprivate _b = 2;\n");
+        let mut report = Report::new();
+        report.warn(warning("TW1", &file, 0..7));
+        report.warn(warning("TW1", &file, 0..7));
+        let baseline = baseline("filter", &report);
+
+        report.warn(warning("TW2", &file, 16..23));
+        report.error(warning("TE1", &file, 0..7));
+        let filtered = baseline.filter(&report);
+        // each entry hides one warning, errors are never hidden
+        assert_eq!(idents(&filtered), vec!["TW2"]);
+        assert_eq!(filtered.errors(WithIncludes::Yes).len(), 1);
+        assert!(baseline.unmatched().is_empty());
+    }
+
+    #[test]
+    fn unmatched() {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let file = source(&workspace, "test.sqf", "private _a = 1;\nprivate _b = 2;\n");
+        let mut report = Report::new();
+        report.warn(warning("TW1", &file, 0..7));
+        report.warn(warning("TW2", &file, 16..23));
+        let baseline = baseline("unmatched", &report);
+
+        // the second line was fixed
+        source(&workspace, "test.sqf", "private _a = 1;\n");
+        let mut report = Report::new();
+        report.warn(warning("TW1", &file, 0..7));
+        assert!(idents(&baseline.filter(&report)).is_empty());
+        let unmatched = baseline.unmatched();
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].code(), "TW2");
+        assert_eq!(unmatched[0].file(), "test.sqf");
+    }
+
+    #[test]
+    fn line_shift() {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let file = source(&workspace, "test.sqf", "private _a = 1;\nprivate _b = 2;\n");
+        let mut report = Report::new();
+        report.warn(warning("TW1", &file, 16..23));
+        let baseline = baseline("line_shift", &report);
+
+        // lines added above the warning do not change its fingerprint
+        source(
+            &workspace,
+            "test.sqf",
+            "// a comment\n\nprivate _a = 1;\nprivate _b = 2;\n",
+        );
+        let mut report = Report::new();
+        report.warn(warning("TW1", &file, 30..37));
+        assert!(idents(&baseline.filter(&report)).is_empty());
+        assert!(baseline.unmatched().is_empty());
+    }
+
+    #[test]
+    fn write() {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let main = source(&workspace, "main.sqf", "private _a = 1;\n");
+        let other = source(&workspace, "other.sqf", "private _b = 2;\n");
+        let mut report = Report::new();
+        report.warn(warning("TW2", &main, 0..7));
+        // reported in `other.sqf`, the primary label, even with a label in `main.sqf` first
+        report.warn(Arc::new(Warning {
+            ident: "TW1",
+            path: other,
+            span: 0..7,
+            related: Some(main.clone()),
+        }));
+        report.warn(warning("TW1", &main, 0..7));
+        let recording = Baseline::default();
+        let _ = recording.filter(&report);
+
+        let folder = std::env::temp_dir().join("hemtt-test-baseline-write");
+        std::fs::create_dir_all(&folder).unwrap();
+        let (path, count) = recording.write(&folder).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&folder).unwrap();
+        assert_eq!(count, 3);
+        assert!(content.starts_with(
+            "# Warnings that are not reported, written by `hemtt check --write-baseline`\n\n"
+        ));
+        let entries = content
+            .lines()
+            .filter(|line| line.starts_with("code") || line.starts_with("file"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![
+                "code = \"TW1\"",
+                "file = \"main.sqf\"",
+                "code = \"TW2\"",
+                "file = \"main.sqf\"",
+                "code = \"TW1\"",
+                "file = \"other.sqf\"",
+            ]
+        );
+    }
+}
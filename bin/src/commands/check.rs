@@ -1,24 +1,67 @@
-use clap::Command;
+use clap::{ArgAction, ArgMatches, Command};
 
 use crate::{
+    baseline::Baseline,
     context::Context,
     error::Error,
     executor::Executor,
+    fix, includes,
     modules::{pbo::Collapse, Hooks, Rapifier, SQFCompiler},
     report::Report,
 };
 
 #[must_use]
 pub fn cli() -> Command {
-    Command::new("check").about("Check the project for errors")
+    Command::new("check")
+        .about("Check the project for errors")
+        .arg(
+        clap::Arg::new("changed-since")
+            .long("changed-since")
+            .value_name("GIT-REF")
+            .help(
+                "Only check files changed since a git reference, and the files that include them",
+            ),
+    )
+    .arg(
+        clap::Arg::new("write-baseline")
+            .long("write-baseline")
+            .help("Record the current warnings in .hemtt/baseline.toml, so they are not reported")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("changed-since"),
+    )
+    .arg(
+        clap::Arg::new("fix")
+            .long("fix")
+            .help("Apply the fixes for warnings and errors that can be fixed safely")
+            .action(ArgAction::SetTrue),
+    )
+    .arg(
+        clap::Arg::new("dry-run")
+            .long("dry-run")
+            .help("Print the changes --fix would make, without writing them")
+            .action(ArgAction::SetTrue)
+            .requires("fix"),
+    )
 }
 
-/// Execute the dev command
+/// Execute the check command
 ///
 /// # Errors
 /// [`Error`] depending on the modules
-pub fn execute() -> Result<Report, Error> {
-    let ctx = Context::new("check", crate::context::PreservePrevious::Remove, true)?;
+pub fn execute(matches: &ArgMatches) -> Result<Report, Error> {
+    let mut ctx = Context::new("check", crate::context::PreservePrevious::Remove, true)?;
+
+    if let Some(reference) = matches.get_one::<String>("changed-since") {
+        let changed = includes::changed_since(ctx.project_folder(), reference)?;
+        if !ctx.include_graph().loaded() {
+            warn!("no include graph from a previous run was found, checking every file");
+        } else if changed.iter().any(|file| file.starts_with("/.hemtt/")) {
+            info!("project configuration changed, checking every file");
+        } else {
+            info!("{} files changed since {}", changed.len(), reference);
+            ctx = ctx.changed(&changed);
+        }
+    }
 
     let mut executor = Executor::new(ctx);
 
@@ -30,9 +73,90 @@ pub fn execute() -> Result<Report, Error> {
 
     info!("Running checks");
 
+    let write_baseline = matches.get_flag("write-baseline");
+    if write_baseline {
+        // every warning is reported, to be recorded in the new baseline
+        executor.set_baseline(Baseline::default());
+    }
+
     executor.init();
     executor.check();
     executor.build(false);
 
-    executor.run()
+    let mut report = executor.run()?;
+
+    if matches.get_flag("fix") {
+        report = apply_fixes(&executor, report, matches.get_flag("dry-run"))?;
+    }
+
+    if write_baseline {
+        if report.failed() {
+            warn!("the baseline was not written, as the checks failed");
+        } else {
+            let (path, count) = executor.baseline().write(executor.ctx().hemtt_folder())?;
+            info!("Wrote {} warnings to {}", count, path.display());
+        }
+    } else if !report.failed() && !executor.ctx().is_partial() {
+        let unmatched = executor.baseline().unmatched();
+        if !unmatched.is_empty() {
+            info!(
+                "{} baselined warnings no longer occur, run `hemtt check --write-baseline` to remove them",
+                unmatched.len()
+            );
+            for entry in unmatched {
+                if entry.file().is_empty() {
+                    info!("  {}", entry.code());
+                } else {
+                    info!("  {} in {}", entry.code(), entry.file());
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Apply the fixes in a report, or print them for a dry run
+///
+/// Returns the report without the codes that were fixed
+fn apply_fixes(executor: &Executor, report: Report, dry_run: bool) -> Result<Report, Error> {
+    let planned = fix::plan(&report)?;
+    if planned.is_empty() {
+        info!("No fixes to apply");
+        return Ok(report);
+    }
+    if dry_run {
+        for file in &planned {
+            println!("{}", file.diff());
+        }
+        info!(
+            "{} fixes would be applied to {} files",
+            planned
+                .iter()
+                .map(|file| file.applied().len())
+                .sum::<usize>(),
+            planned.len()
+        );
+        return Ok(report);
+    }
+    let mut written = Vec::new();
+    for file in planned {
+        if file.write(executor.ctx().project_folder())? {
+            written.push(file);
+        } else {
+            warn!(
+                "{} was changed while checking, it was not fixed",
+                file.path()
+            );
+        }
+    }
+    info!(
+        "Applied {} fixes to {} files",
+        written
+            .iter()
+            .map(|file| file.applied().len())
+            .sum::<usize>(),
+        written.len()
+    );
+    Ok(fix::without_fixed(&report, &written))
 }
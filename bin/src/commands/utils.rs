@@ -8,9 +8,12 @@ pub fn cli() -> Command {
         .about("Use HEMTT standalone utils")
         .subcommand_required(false)
         .arg_required_else_help(true)
+        .subcommand(utils::config::cli())
         .subcommand(utils::inspect::cli())
         .subcommand(utils::paa::cli())
         .subcommand(utils::pbo::cli())
+        .subcommand(utils::preprocess::cli())
+        .subcommand(utils::sqf::cli())
         .subcommand(utils::verify::cli())
 }
 
@@ -20,11 +23,13 @@ pub fn cli() -> Command {
 /// [`Error`] depending on the modules
 pub fn execute(matches: &ArgMatches) -> Result<Report, Error> {
     match matches.subcommand() {
-        Some(("inspect", matches)) => utils::inspect::execute(matches),
-        Some(("paa", matches)) => utils::paa::execute(matches),
-        Some(("pbo", matches)) => utils::pbo::execute(matches),
-        Some(("verify", matches)) => utils::verify::execute(matches),
+        Some(("config", matches)) => utils::config::execute(matches),
+        Some(("inspect", matches)) => utils::inspect::execute(matches).map(|()| Report::new()),
+        Some(("paa", matches)) => utils::paa::execute(matches).map(|()| Report::new()),
+        Some(("pbo", matches)) => utils::pbo::execute(matches).map(|()| Report::new()),
+        Some(("preprocess", matches)) => utils::preprocess::execute(matches),
+        Some(("sqf", matches)) => utils::sqf::execute(matches),
+        Some(("verify", matches)) => utils::verify::execute(matches).map(|()| Report::new()),
         _ => unreachable!(),
-    }?;
-    Ok(Report::new())
+    }
 }
@@ -1,13 +1,16 @@
 use std::{
+    collections::{HashMap, HashSet},
     env::temp_dir,
     fs::{create_dir_all, remove_dir_all},
     path::PathBuf,
+    sync::Arc,
 };
 
 use hemtt_common::workspace::{LayerType, Workspace, WorkspacePath};
-use hemtt_common::{addons::Addon, project::ProjectConfig};
+use hemtt_common::{addons::Addon, project::ProjectConfig, version::Version};
+use hemtt_preprocessor::Cache;
 
-use crate::error::Error;
+use crate::{error::Error, includes::IncludeGraph};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Should the current contents of .hemttout\{} be preserved
@@ -29,6 +32,9 @@ pub struct Context {
     out_folder: PathBuf,
     build_folder: PathBuf,
     tmp: PathBuf,
+    preprocessor_cache: Arc<Cache>,
+    include_graph: Arc<IncludeGraph>,
+    selected: Option<Arc<HashSet<String>>>,
 }
 
 impl Context {
@@ -113,9 +119,12 @@ impl Context {
             addons: Addon::scan(&root)?,
             project_folder: root,
             hemtt_folder,
-            out_folder,
             build_folder,
             tmp,
+            preprocessor_cache: Arc::new(Cache::new()),
+            include_graph: Arc::new(IncludeGraph::load(&out_folder)),
+            selected: None,
+            out_folder,
         })
     }
 
@@ -135,6 +144,29 @@ impl Context {
         }
     }
 
+    #[must_use]
+    /// Only check the files that were changed, and the files that include them
+    pub fn changed(self, changed: &HashSet<String>) -> Self {
+        Self {
+            selected: Some(Arc::new(self.include_graph.dependents(changed))),
+            ..self
+        }
+    }
+
+    #[must_use]
+    /// Should a file be checked, `false` if it was not changed and includes no changed files
+    pub fn is_selected(&self, path: &WorkspacePath) -> bool {
+        self.selected
+            .as_ref()
+            .is_none_or(|selected| selected.contains(path.as_str()))
+    }
+
+    #[must_use]
+    /// Are only some of the files being checked
+    pub const fn is_partial(&self) -> bool {
+        self.selected.is_some()
+    }
+
     #[must_use]
     pub const fn config(&self) -> &ProjectConfig {
         &self.config
@@ -155,6 +187,56 @@ impl Context {
         self.addons.iter().find(|a| a.name() == name)
     }
 
+    #[must_use]
+    /// Macros to define before preprocessing a file of an addon
+    ///
+    /// The project's defines are applied first and then the addon's,
+    /// each followed by the defines of the current mode, such as `dev` or `release`.
+    pub fn defines(&self, addon: &Addon) -> HashMap<String, String> {
+        let mut defines = HashMap::new();
+        self.config.preprocessor().apply(&self.folder, &mut defines);
+        if let Some(config) = addon.config() {
+            config.preprocessor().apply(&self.folder, &mut defines);
+        }
+        defines
+    }
+
+    /// HEMTT's builtin macros with the version and build info of the project
+    ///
+    /// # Errors
+    /// [`Error`] if the version of the project could not be read
+    pub fn builtins(&self, addon: &Addon) -> Result<HashMap<String, String>, Error> {
+        let version = self.config.version().get(self.workspace.vfs())?;
+        Ok(builtins(
+            self.config.prefix(),
+            &version,
+            addon.name(),
+            &self.folder,
+        ))
+    }
+
+    /// The defines of an addon and HEMTT's builtin macros, that every file of the addon is preprocessed with
+    ///
+    /// # Errors
+    /// [`Error`] if the version of the project could not be read
+    pub fn defines_with_builtins(&self, addon: &Addon) -> Result<HashMap<String, String>, Error> {
+        let mut defines = self.defines(addon);
+        defines.extend(self.builtins(addon)?);
+        Ok(defines)
+    }
+
+    #[must_use]
+    /// Tokenized include files, shared by every file preprocessed in the build
+    pub const fn preprocessor_cache(&self) -> &Arc<Cache> {
+        &self.preprocessor_cache
+    }
+
+    #[must_use]
+    /// The files used by each preprocessed file, kept between runs
+    pub const fn include_graph(&self) -> &Arc<IncludeGraph> {
+        &self.include_graph
+    }
+
     #[must_use]
     pub const fn workspace(&self) -> &WorkspacePath {
         &self.workspace
@@ -190,3 +272,81 @@ impl Context {
         &self.tmp
     }
 }
+
+fn builtins(prefix: &str, version: &Version, addon: &str, mode: &str) -> HashMap<String, String> {
+    let version_str = format!(
+        "{}.{}.{}",
+        version.major(),
+        version.minor(),
+        version.patch()
+    );
+    let version_str = version.build().map_or_else(
+        || version_str.clone(),
+        |build| format!("{version_str}.{build}"),
+    );
+    let mut builtins = HashMap::from([
+        ("__HEMTT_PROJECT_VERSION__".to_string(), version_str),
+        (
+            "__HEMTT_PROJECT_VERSION_MAJ__".to_string(),
+            version.major().to_string(),
+        ),
+        (
+            "__HEMTT_PROJECT_VERSION_MIN__".to_string(),
+            version.minor().to_string(),
+        ),
+        (
+            "__HEMTT_PROJECT_VERSION_PAT__".to_string(),
+            version.patch().to_string(),
+        ),
+        (
+            "__HEMTT_PROJECT_VERSION_BUILD__".to_string(),
+            version.build().unwrap_or_default().to_string(),
+        ),
+        ("__HEMTT_PROJECT_PREFIX__".to_string(), prefix.to_string()),
+        ("__HEMTT_ADDON__".to_string(), addon.to_string()),
+        ("__HEMTT_MODE__".to_string(), mode.to_string()),
+    ]);
+    if let Some(hash) = version.hash() {
+        builtins.insert("__HEMTT_PROJECT_GIT_HASH__".to_string(), hash.to_string());
+    }
+    builtins
+}
+
+#[cfg(test)]
+mod tests {
+    use hemtt_common::version::Version;
+
+    use super::builtins;
+
+    #[test]
+    fn builtins_version() {
+        let mut version = Version::new(1, 2, 3, Some(45));
+        version.set_build("abc1234");
+        let builtins = builtins("abe", &version, "main", "release");
+        for (name, value) in [
+            ("__HEMTT_PROJECT_VERSION__", "1.2.3.45"),
+            ("__HEMTT_PROJECT_VERSION_MAJ__", "1"),
+            ("__HEMTT_PROJECT_VERSION_MIN__", "2"),
+            ("__HEMTT_PROJECT_VERSION_PAT__", "3"),
+            ("__HEMTT_PROJECT_VERSION_BUILD__", "45"),
+            ("__HEMTT_PROJECT_PREFIX__", "abe"),
+            ("__HEMTT_ADDON__", "main"),
+            ("__HEMTT_MODE__", "release"),
+            ("__HEMTT_PROJECT_GIT_HASH__", "abc1234"),
+        ] {
+            assert_eq!(
+                builtins.get(name).map(String::as_str),
+                Some(value),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn builtins_without_build() {
+        let builtins = builtins("abe", &Version::new(1, 2, 3, None), "main", "dev");
+        assert_eq!(builtins["__HEMTT_PROJECT_VERSION__"], "1.2.3");
+        assert_eq!(builtins["__HEMTT_PROJECT_VERSION_BUILD__"], "0");
+        assert!(!builtins.contains_key("__HEMTT_PROJECT_GIT_HASH__"));
+    }
+}
@@ -31,6 +31,11 @@ pub enum Error {
     #[error("Addon Error: {0}")]
     Addon(#[from] hemtt_common::addons::Error),
 
+    #[error("{0} file(s) are not formatted")]
+    Unformatted(usize),
+    #[error("{0} file(s) could not be formatted")]
+    FormatSkipped(usize),
+
     #[error("Update error: {0}")]
     Update(String),
 
@@ -46,6 +51,10 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("serde_json Error: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("TOML Error: {0}")]
+    TomlDe(#[from] toml::de::Error),
+    #[error("TOML Error: {0}")]
+    TomlSer(#[from] toml::ser::Error),
     #[error("Vfs Error {0}")]
     Vfs(Box<vfs::VfsError>),
     #[error("Walkdir Error: {0}")]
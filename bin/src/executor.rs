@@ -1,3 +1,4 @@
+use crate::baseline::Baseline;
 use crate::error::Error;
 
 use crate::report::Report;
@@ -11,16 +12,22 @@ pub struct Executor {
     modules: Vec<Box<dyn Module>>,
     collapse: Collapse,
     stages: Vec<&'static str>,
+    baseline: Baseline,
 }
 
 impl Executor {
     #[must_use]
     pub fn new(ctx: Context) -> Self {
+        let baseline = Baseline::load(ctx.hemtt_folder()).unwrap_or_else(|e| {
+            error!("Failed to read .hemtt/baseline.toml, every warning will be reported: {e}");
+            Baseline::default()
+        });
         Self {
             ctx,
             modules: Vec::new(),
             collapse: Collapse::Yes,
             stages: Vec::new(),
+            baseline,
         }
     }
 
@@ -29,6 +36,17 @@ impl Executor {
         &self.ctx
     }
 
+    #[must_use]
+    /// Warnings from `.hemtt/baseline.toml`, that are not reported
+    pub const fn baseline(&self) -> &Baseline {
+        &self.baseline
+    }
+
+    /// Replace the baseline, such as with an empty one to report every warning
+    pub fn set_baseline(&mut self, baseline: Baseline) {
+        self.baseline = baseline;
+    }
+
     pub fn collapse(&mut self, collpase: Collapse) {
         self.collapse = collpase;
     }
@@ -80,7 +98,7 @@ impl Executor {
                     trace!("phase: build (start)");
                     let report = modules::pbo::build(&self.ctx, self.collapse)?;
                     trace!("phase: build (done)");
-                    report
+                    self.lint(&report)
                 }
                 "post_build" => self.run_modules("post_build")?,
                 "pre_release" => self.run_modules("pre_release")?,
@@ -88,7 +106,7 @@ impl Executor {
                     trace!("phase: release (start)");
                     let report = modules::archive::release(&self.ctx)?;
                     trace!("phase: release (done)");
-                    report
+                    self.lint(&report)
                 }
                 "post_release" => self.run_modules("post_release")?,
                 _ => unreachable!(),
@@ -97,14 +115,22 @@ impl Executor {
                 break;
             }
         }
+        self.ctx.include_graph().save(self.ctx.out_folder())?;
         Ok(report)
     }
 
+    /// Removes baselined warnings from a report, and applies the `[lints]` table of project.toml
+    fn lint(&self, report: &Report) -> Report {
+        self.baseline
+            .filter(report)
+            .with_lints(self.ctx.config().lints(), self.ctx.addons())
+    }
+
     fn run_modules(&mut self, phase: &str) -> Result<Report, Error> {
         let mut report = Report::new();
         for module in &mut self.modules {
             trace!("phase: {} ({}) (start)", phase, module.name());
-            report.merge(match phase {
+            let module_report = match phase {
                 "init" => module.init(&self.ctx)?,
                 "check" => module.check(&self.ctx)?,
                 "pre_build" => module.pre_build(&self.ctx)?,
@@ -112,7 +138,12 @@ impl Executor {
                 "pre_release" => module.pre_release(&self.ctx)?,
                 "post_release" => module.post_release(&self.ctx)?,
                 _ => unreachable!(),
-            });
+            };
+            report.merge(
+                self.baseline
+                    .filter(&module_report)
+                    .with_lints(self.ctx.config().lints(), self.ctx.addons()),
+            );
             if report.failed() {
                 trace!("phase: {} ({}) (failed)", phase, module.name());
                 break;
@@ -0,0 +1,242 @@
+//! Fixes from codes, applied to the files of the project by `hemtt check --fix`
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+    path::Path,
+    sync::Arc,
+};
+
+use hemtt_common::{
+    reporting::{Code, Fix},
+    workspace::WorkspacePath,
+};
+
+use crate::{
+    error::Error,
+    report::{Report, WithIncludes},
+};
+
+/// The fixes for a single file
+pub struct FileFixes {
+    path: WorkspacePath,
+    original: String,
+    fixed: String,
+    applied: Vec<Fix>,
+}
+
+impl FileFixes {
+    #[must_use]
+    /// The file that is fixed
+    pub const fn path(&self) -> &WorkspacePath {
+        &self.path
+    }
+
+    #[must_use]
+    /// The fixes that are applied to the file
+    pub fn applied(&self) -> &[Fix] {
+        &self.applied
+    }
+
+    #[must_use]
+    /// A unified diff of the changes to the file
+    pub fn diff(&self) -> String {
+        diff(self.path.as_str(), &self.original, &self.applied)
+    }
+
+    /// Write the fixed file to the project
+    ///
+    /// Returns `false` if the file on disk is not the file that was checked,
+    /// such as when it was changed by a hook
+    ///
+    /// # Errors
+    /// [`Error::Io`] if the file can not be read or written
+    pub fn write(&self, project_folder: &Path) -> Result<bool, Error> {
+        let path = project_folder.join(self.path.as_str().trim_start_matches('/'));
+        if std::fs::read_to_string(&path)? != self.original {
+            return Ok(false);
+        }
+        std::fs::write(path, &self.fixed)?;
+        Ok(true)
+    }
+}
+
+/// Collect the fixes of every code in a report, grouped by file
+///
+/// Fixes that overlap an earlier fix in the same file are left for a later run
+///
+/// # Errors
+/// [`Error::Workspace`] if a file can not be read
+pub fn plan(report: &Report) -> Result<Vec<FileFixes>, Error> {
+    let mut seen = HashSet::new();
+    let mut files: BTreeMap<String, Vec<Fix>> = BTreeMap::new();
+    for code in report
+        .warnings(WithIncludes::No)
+        .into_iter()
+        .chain(report.errors(WithIncludes::No))
+    {
+        let Some(fix) = code.fix() else {
+            continue;
+        };
+        if seen.insert(fix.clone()) {
+            files
+                .entry(fix.file().as_str().to_string())
+                .or_default()
+                .push(fix);
+        }
+    }
+    let mut planned = Vec::new();
+    for fixes in files.into_values() {
+        let path = fixes[0].file().clone();
+        let original = path.read_to_string()?;
+        let (fixed, applied) = apply(&original, fixes);
+        if !applied.is_empty() {
+            planned.push(FileFixes {
+                path,
+                original,
+                fixed,
+                applied,
+            });
+        }
+    }
+    Ok(planned)
+}
+
+#[must_use]
+/// Remove the codes that were fixed from a report
+pub fn without_fixed(report: &Report, fixed: &[FileFixes]) -> Report {
+    let fixed = fixed
+        .iter()
+        .flat_map(|file| file.applied.iter())
+        .collect::<HashSet<_>>();
+    let is_fixed = |code: &Arc<dyn Code>| code.fix().is_some_and(|fix| fixed.contains(&fix));
+    let mut remaining = Report::new();
+    remaining.add_warnings(
+        report
+            .warnings(WithIncludes::Yes)
+            .into_iter()
+            .filter(|code| !is_fixed(code))
+            .collect(),
+    );
+    remaining.add_errors(
+        report
+            .errors(WithIncludes::Yes)
+            .into_iter()
+            .filter(|code| !is_fixed(code))
+            .collect(),
+    );
+    remaining
+}
+
+/// Apply fixes to the content of a file, skipping fixes that overlap or do not fit the content
+///
+/// Returns the fixed content and the fixes that were applied, in order
+fn apply(content: &str, mut fixes: Vec<Fix>) -> (String, Vec<Fix>) {
+    fixes.sort_by_key(|fix| (fix.span().start, fix.span().end));
+    let mut applied: Vec<Fix> = Vec::new();
+    for fix in fixes {
+        let span = fix.span();
+        if span.end > content.len()
+            || !content.is_char_boundary(span.start)
+            || !content.is_char_boundary(span.end)
+            || applied
+                .last()
+                .is_some_and(|last| last.span().end > span.start)
+        {
+            continue;
+        }
+        applied.push(fix);
+    }
+    let mut output = content.to_string();
+    for fix in applied.iter().rev() {
+        output.replace_range(fix.span().clone(), fix.replacement());
+    }
+    (output, applied)
+}
+
+/// A unified diff of the lines changed by fixes, which must be sorted and not overlap
+fn diff(path: &str, content: &str, fixes: &[Fix]) -> String {
+    let line_of = |offset: usize| content[..offset].matches('\n').count();
+    let line_start = |offset: usize| content[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = |offset: usize| {
+        content[offset..]
+            .find('\n')
+            .map_or(content.len(), |i| offset + i + 1)
+    };
+    // group fixes that change the same lines into one hunk
+    let mut hunks: Vec<(usize, usize, Vec<&Fix>)> = Vec::new();
+    for fix in fixes {
+        let start = line_start(fix.span().start);
+        let last = fix.span().end.saturating_sub(1).max(fix.span().start);
+        let end = line_end(last.min(content.len()));
+        match hunks.last_mut() {
+            Some(hunk) if hunk.1 > start => {
+                hunk.1 = hunk.1.max(end);
+                hunk.2.push(fix);
+            }
+            _ => hunks.push((start, end, vec![fix])),
+        }
+    }
+    let mut out = format!("--- a{path}\n+++ b{path}\n");
+    // lines added and removed by earlier hunks
+    let (mut added, mut removed) = (0, 0);
+    for (start, end, fixes) in hunks {
+        let old = &content[start..end];
+        let mut new = old.to_string();
+        for fix in fixes.iter().rev() {
+            new.replace_range(
+                fix.span().start - start..fix.span().end - start,
+                fix.replacement(),
+            );
+        }
+        let old_lines = old.lines().count();
+        let new_lines = new.lines().count();
+        let line = line_of(start) + 1;
+        let _ = writeln!(
+            out,
+            "@@ -{},{} +{},{} @@",
+            line,
+            old_lines,
+            line + added - removed,
+            new_lines
+        );
+        for old in old.lines() {
+            let _ = writeln!(out, "-{old}");
+        }
+        for new in new.lines() {
+            let _ = writeln!(out, "+{new}");
+        }
+        added += new_lines;
+        removed += old_lines;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use hemtt_common::{reporting::Fix, workspace::Workspace};
+
+    use super::{apply, diff};
+
+    #[test]
+    fn overlapping() {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let path = workspace.join("test.hpp").unwrap();
+        let content = "class A: a {};\nclass B: A {\n    value = FOO( 1);\n};\n";
+        let edits = vec![
+            Fix::new(path.clone(), 44..45, String::new()),
+            Fix::new(path.clone(), 9..10, "A".to_string()),
+            Fix::new(path.clone(), 9..11, String::new()),
+        ];
+        let (fixed, applied) = apply(content, edits);
+        assert_eq!(
+            fixed,
+            "class A: A {};\nclass B: A {\n    value = FOO(1);\n};\n"
+        );
+        assert_eq!(applied.len(), 2);
+        assert_eq!(
+            diff(path.as_str(), content, &applied),
+            "--- a/test.hpp\n+++ b/test.hpp\n@@ -1,1 +1,1 @@\n-class A: a {};\n+class A: A {};\n@@ -3,1 +3,1 @@\n-    value = FOO( 1);\n+    value = FOO(1);\n"
+        );
+    }
+}
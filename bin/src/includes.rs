@@ -0,0 +1,259 @@
+//! The files included by each preprocessed file, kept between runs in `.hemttout`
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::Path,
+    sync::RwLock,
+};
+
+use git2::{DiffOptions, Repository};
+use hemtt_common::{reporting::Processed, workspace::WorkspacePath};
+
+use crate::error::Error;
+
+const FILE: &str = "includes.json";
+
+#[derive(Debug, Default)]
+/// The files used by each preprocessed file, including files included by its includes
+pub struct IncludeGraph {
+    files: RwLock<BTreeMap<String, BTreeSet<String>>>,
+    /// Was the graph read from a previous run
+    loaded: bool,
+}
+
+impl IncludeGraph {
+    #[must_use]
+    /// Read the graph written by a previous run, or start an empty one
+    pub fn load(out_folder: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(out_folder.join(FILE)) else {
+            return Self::default();
+        };
+        match serde_json::from_str(&content) {
+            Ok(files) => Self {
+                files: RwLock::new(files),
+                loaded: true,
+            },
+            Err(e) => {
+                debug!("ignoring invalid include graph: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Write the graph to `.hemttout`, for the next run
+    ///
+    /// # Errors
+    /// [`Error::Io`] if the file can not be written
+    ///
+    /// # Panics
+    /// If the lock is poisoned
+    pub fn save(&self, out_folder: &Path) -> Result<(), Error> {
+        std::fs::write(
+            out_folder.join(FILE),
+            serde_json::to_string_pretty(&*self.files.read().unwrap())?,
+        )?;
+        Ok(())
+    }
+
+    /// Record the files included while preprocessing a file
+    ///
+    /// # Panics
+    /// If the lock is poisoned
+    pub fn record(&self, path: &WorkspacePath, processed: &Processed) {
+        let sources = processed
+            .includes()
+            .iter()
+            .filter(|source| *source != path)
+            .map(|source| source.as_str().to_string())
+            .collect();
+        self.files
+            .write()
+            .unwrap()
+            .insert(path.as_str().to_string(), sources);
+    }
+
+    #[must_use]
+    /// Was the graph read from a previous run
+    pub const fn loaded(&self) -> bool {
+        self.loaded
+    }
+
+    #[must_use]
+    /// The files that were changed, and every file that includes one of them
+    ///
+    /// A changed `addon.toml` can change the defines of every file in the addon,
+    /// so they are all included
+    ///
+    /// # Panics
+    /// If the lock is poisoned
+    pub fn dependents(&self, changed: &HashSet<String>) -> HashSet<String> {
+        let mut dependents = changed.clone();
+        let addons = changed
+            .iter()
+            .filter_map(|file| file.strip_suffix("addon.toml"))
+            .filter(|folder| folder.ends_with('/'))
+            .collect::<Vec<_>>();
+        for (file, sources) in self.files.read().unwrap().iter() {
+            if sources.iter().any(|source| changed.contains(source))
+                || addons.iter().any(|folder| file.starts_with(folder))
+            {
+                dependents.insert(file.clone());
+            }
+        }
+        dependents
+    }
+}
+
+/// The files of the project changed since a git reference,
+/// including changes that are not committed yet
+///
+/// Paths are returned as they appear in the workspace, such as `/addons/main/config.cpp`
+///
+/// # Errors
+/// [`Error::Git`] if the repository or reference can not be found
+pub fn changed_since(project_folder: &Path, reference: &str) -> Result<HashSet<String>, Error> {
+    let repo = Repository::discover(project_folder)?;
+    let tree = repo.revparse_single(reference)?.peel_to_tree()?;
+    let diff = repo.diff_tree_to_workdir_with_index(
+        Some(&tree),
+        Some(
+            DiffOptions::new()
+                .include_untracked(true)
+                .recurse_untracked_dirs(true),
+        ),
+    )?;
+    let project = repo
+        .workdir()
+        .and_then(|workdir| {
+            project_folder
+                .canonicalize()
+                .ok()?
+                .strip_prefix(workdir.canonicalize().ok()?)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .unwrap_or_default();
+    let mut changed = HashSet::new();
+    for delta in diff.deltas() {
+        for file in [delta.old_file().path(), delta.new_file().path()]
+            .into_iter()
+            .flatten()
+        {
+            let Ok(file) = file.strip_prefix(&project) else {
+                continue;
+            };
+            let file = file.to_string_lossy().replace('\\', "/");
+            // files in the include folder are in the root of the workspace
+            if let Some(include) = file.strip_prefix("include/") {
+                changed.insert(format!("/{include}"));
+            }
+            changed.insert(format!("/{file}"));
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+    use git2::{Repository, Signature};
+
+    use super::{changed_since, IncludeGraph};
+
+    fn graph() -> IncludeGraph {
+        IncludeGraph {
+            files: BTreeMap::from([
+                (
+                    "/addons/main/config.cpp".to_string(),
+                    BTreeSet::from([
+                        "/addons/main/script_component.hpp".to_string(),
+                        "/addons/main/CfgEventHandlers.hpp".to_string(),
+                    ]),
+                ),
+                (
+                    "/addons/main/functions/fnc_test.sqf".to_string(),
+                    BTreeSet::from(["/addons/main/script_component.hpp".to_string()]),
+                ),
+            ])
+            .into(),
+            loaded: true,
+        }
+    }
+
+    #[test]
+    fn dependents() {
+        let graph = graph();
+        let changed = graph.dependents(&HashSet::from([
+            "/addons/main/CfgEventHandlers.hpp".to_string()
+        ]));
+        assert_eq!(changed.len(), 2);
+        assert!(changed.contains("/addons/main/config.cpp"));
+        let changed = graph.dependents(&HashSet::from([
+            "/addons/main/script_component.hpp".to_string()
+        ]));
+        assert_eq!(changed.len(), 3);
+    }
+
+    #[test]
+    fn dependents_addon_config() {
+        let changed = graph().dependents(&HashSet::from(["/addons/main/addon.toml".to_string()]));
+        assert_eq!(changed.len(), 3);
+        assert!(changed.contains("/addons/main/functions/fnc_test.sqf"));
+        let changed = graph().dependents(&HashSet::from(["/addons/other/addon.toml".to_string()]));
+        assert_eq!(changed.len(), 1);
+    }
+
+    #[test]
+    fn changed_files() {
+        let root = std::env::temp_dir().join("hemtt-test-changed-since");
+        if root.exists() {
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+        let project = root.join("project");
+        std::fs::create_dir_all(project.join("addons/main")).unwrap();
+        std::fs::create_dir_all(project.join("include/abe")).unwrap();
+        std::fs::write(
+            project.join("addons/main/config.cpp"),
+            "class CfgPatches {};\n",
+        )
+        .unwrap();
+        std::fs::write(project.join("addons/main/unchanged.sqf"), "true\n").unwrap();
+        std::fs::write(project.join("include/abe/macros.hpp"), "#define A 1\n").unwrap();
+        std::fs::write(root.join("README.md"), "outside the project\n").unwrap();
+
+        let repo = Repository::init(&root).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+            .unwrap();
+
+        // modified, untracked, and outside of the project
+        std::fs::write(
+            project.join("addons/main/config.cpp"),
+            "class CfgPatches {}; \n",
+        )
+        .unwrap();
+        std::fs::write(project.join("addons/main/new.sqf"), "false\n").unwrap();
+        std::fs::write(project.join("include/abe/macros.hpp"), "#define A 2\n").unwrap();
+        std::fs::write(root.join("README.md"), "changed\n").unwrap();
+
+        let changed = changed_since(&project, "HEAD").unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(
+            changed,
+            HashSet::from([
+                "/addons/main/config.cpp".to_string(),
+                "/addons/main/new.sqf".to_string(),
+                "/include/abe/macros.hpp".to_string(),
+                // include files are in the root of the workspace
+                "/abe/macros.hpp".to_string(),
+            ])
+        );
+    }
+}
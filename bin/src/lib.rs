@@ -1,13 +1,17 @@
 use clap::{ArgAction, ArgMatches, Command};
 pub use error::Error;
+use hemtt_common::{project::ProjectConfig, reporting::ReportFormat};
 
 #[macro_use]
 extern crate tracing;
 
+pub mod baseline;
 pub mod commands;
 pub mod context;
 pub mod error;
 pub mod executor;
+pub mod fix;
+pub mod includes;
 pub mod link;
 pub mod logging;
 pub mod modules;
@@ -40,6 +44,15 @@ pub fn cli() -> Command {
                 .long("threads")
                 .short('t'),
         )
+        .arg(
+            clap::Arg::new("report-format")
+                .global(true)
+                .help("Also write the report to .hemttout in these formats")
+                .long("report-format")
+                .value_parser(["json", "sarif", "gitlab"])
+                .value_delimiter(',')
+                .action(ArgAction::Append),
+        )
         .arg(
             clap::Arg::new("verbosity")
                 .global(true)
@@ -114,7 +127,7 @@ pub fn execute(matches: &ArgMatches) -> Result<(), Error> {
     let report = match matches.subcommand() {
         Some(("new", matches)) => commands::new::execute(matches).map(Some),
         Some(("dev", matches)) => commands::dev::execute(matches, &[]).map(Some),
-        Some(("check", _matches)) => commands::check::execute().map(Some),
+        Some(("check", matches)) => commands::check::execute(matches).map(Some),
         Some(("build", matches)) => commands::build::execute(matches)
             .map_err(std::convert::Into::into)
             .map(Some),
@@ -138,6 +151,7 @@ pub fn execute(matches: &ArgMatches) -> Result<(), Error> {
     if let Some(report) = report? {
         report.write_to_stdout();
         report.write_ci_annotations()?;
+        report.write_formats(&report_formats(matches))?;
         if report.failed() {
             std::process::exit(1);
         }
@@ -145,6 +159,21 @@ pub fn execute(matches: &ArgMatches) -> Result<(), Error> {
     Ok(())
 }
 
+/// The formats to write the report in, from `--report-format`,
+/// or from `hemtt.report_format` in project.toml
+fn report_formats(matches: &ArgMatches) -> Vec<ReportFormat> {
+    if let Some(formats) = matches.get_many::<String>("report-format") {
+        return formats.filter_map(|format| format.parse().ok()).collect();
+    }
+    let path = std::path::Path::new(".hemtt").join("project.toml");
+    if !path.exists() {
+        return Vec::new();
+    }
+    ProjectConfig::from_file(&path)
+        .map(|config| config.hemtt().report_format().to_vec())
+        .unwrap_or_default()
+}
+
 #[must_use]
 pub fn is_ci() -> bool {
     // TODO: replace with crate if a decent one comes along
@@ -85,10 +85,12 @@ impl Module for ArmaScriptCompiler {
                     entries.push(entry);
                 }
             }
+            let defines = ctx.defines_with_builtins(addon)?;
             entries
                 .par_iter()
                 .map(|entry| {
-                    let processed = Processor::run(entry)?;
+                    let processed =
+                        Processor::run_with_defines(entry, &defines, ctx.preprocessor_cache())?;
                     let source = tmp_addon.join(
                         entry
                             .as_str()
@@ -0,0 +1,261 @@
+//! Checks SQF that is embedded in config strings, such as `init` event handlers
+
+use hemtt_common::{addons::Addon, project::ProjectConfig, reporting::Processed};
+use hemtt_config::{Class, Config, Expression, Property, Str, Value};
+use hemtt_sqf::{
+    analyze::{analyze, analyze_unscheduled},
+    parser::{database::Database, ParserError},
+};
+
+use crate::report::Report;
+
+/// SQF found in a config value, with the byte offset in the config of each character
+struct Snippet {
+    code: String,
+    offsets: Vec<usize>,
+    /// Is the code a CBA extended event handler, which runs unscheduled
+    unscheduled: bool,
+}
+
+/// Parses and analyzes the SQF in properties listed by `sqf.config_properties`,
+/// in every string inside `Extended_*_EventHandlers` classes, and in `__EVAL` expressions
+pub fn check(
+    config: &Config,
+    processed: &Processed,
+    project: &ProjectConfig,
+    addon: Option<&Addon>,
+    database: &Database,
+) -> Report {
+    let mut report = Report::new();
+    // config spans count characters, while offsets in the processed config count bytes
+    let chars = processed.as_str().char_indices().collect::<Vec<_>>();
+    let mut found = Vec::new();
+    snippets(
+        &config.0,
+        &project.sqf().config_properties(),
+        false,
+        &chars,
+        &mut found,
+    );
+    for snippet in found {
+        let processed = processed.extract(snippet.code, &snippet.offsets);
+        let statements = match hemtt_sqf::parser::run(database, &processed) {
+            Ok(statements) => statements,
+            Err(ParserError::ParsingError(errors, partial)) => {
+                report.add_errors(errors);
+                let Some(statements) = partial else {
+                    continue;
+                };
+                statements
+            }
+            Err(ParserError::LexingError(errors)) => {
+                report.add_errors(errors);
+                continue;
+            }
+        };
+        let (warnings, errors) = if snippet.unscheduled {
+            analyze_unscheduled(
+                &statements,
+                Some(project),
+                &processed,
+                addon,
+                database,
+                "CBA extended event handlers run unscheduled",
+            )
+        } else {
+            analyze(&statements, Some(project), &processed, addon, database)
+        };
+        report.add_warnings(warnings);
+        report.add_errors(errors);
+    }
+    report
+}
+
+fn snippets(
+    properties: &[Property],
+    code_properties: &[String],
+    event_handlers: bool,
+    chars: &[(usize, char)],
+    found: &mut Vec<Snippet>,
+) {
+    for property in properties {
+        match property {
+            Property::Entry { name, value, .. } => {
+                let snippet = match value {
+                    Value::Str(string)
+                        if event_handlers
+                            || code_properties
+                                .iter()
+                                .any(|code| code.eq_ignore_ascii_case(name.as_str())) =>
+                    {
+                        string_snippet(chars, string).map(|snippet| Snippet {
+                            unscheduled: event_handlers,
+                            ..snippet
+                        })
+                    }
+                    Value::Expression(expression) => expression_snippet(chars, expression),
+                    _ => None,
+                };
+                if let Some(snippet) = snippet {
+                    if !snippet.code.trim().is_empty() {
+                        found.push(snippet);
+                    }
+                }
+            }
+            Property::Class(Class::Local {
+                name, properties, ..
+            }) => {
+                let name = name.as_str().to_lowercase();
+                snippets(
+                    properties,
+                    code_properties,
+                    event_handlers
+                        || (name.starts_with("extended_") && name.ends_with("_eventhandlers")),
+                    chars,
+                    found,
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Maps each character of a string's value back to the literal, skipping the quotes
+/// around each segment and treating doubled quotes as one
+///
+/// Returns `None` if the value can not be mapped exactly
+fn string_snippet(chars: &[(usize, char)], string: &Str) -> Option<Snippet> {
+    let span = string.span();
+    let delimiter = chars.get(span.start)?.1;
+    let mut code = String::new();
+    let mut offsets = Vec::new();
+    let mut in_string = false;
+    let mut i = span.start;
+    while i < span.end.min(chars.len()) {
+        let (offset, c) = chars[i];
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+        if in_string {
+            if c == delimiter && next == Some(delimiter) {
+                code.push(c);
+                offsets.push(offset);
+                i += 1;
+            } else if c == delimiter {
+                in_string = false;
+            } else {
+                code.push(c);
+                offsets.push(offset);
+            }
+        } else if c == delimiter {
+            in_string = true;
+        } else if c == '\\' && next == Some('n') {
+            code.push('\n');
+            offsets.push(offset);
+            i += 1;
+        }
+        i += 1;
+    }
+    (code == string.value()).then_some(Snippet {
+        code,
+        offsets,
+        unscheduled: false,
+    })
+}
+
+/// Maps each character of an `__EVAL` expression back to its place inside the brackets
+///
+/// Returns `None` if the value can not be mapped exactly
+fn expression_snippet(chars: &[(usize, char)], expression: &Expression) -> Option<Snippet> {
+    let span = expression.span();
+    let inner = chars.get(span.start + "__EVAL(".len()..span.end.checked_sub(1)?)?;
+    let code = inner.iter().map(|(_, c)| c).collect::<String>();
+    (code == expression.value()).then(|| Snippet {
+        code,
+        offsets: inner.iter().map(|(offset, _)| *offset).collect(),
+        unscheduled: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use hemtt_common::{project::ProjectConfig, workspace::Workspace};
+    use hemtt_preprocessor::Processor;
+    use hemtt_sqf::parser::database::Database;
+
+    use crate::report::{Report, WithIncludes};
+
+    fn check(source: &str) -> Report {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let path = workspace.join("config.cpp").unwrap();
+        path.create_file()
+            .unwrap()
+            .write_all(source.as_bytes())
+            .unwrap();
+        let processed = Processor::run(&path).unwrap();
+        let project = ProjectConfig::from_str("name = \"Test\"\nprefix = \"test\"").unwrap();
+        let config = hemtt_config::parse(Some(&project), &processed).unwrap();
+        super::check(
+            config.config(),
+            &processed,
+            &project,
+            None,
+            &Database::default(),
+        )
+    }
+
+    fn codes(report: &Report) -> Vec<&'static str> {
+        report
+            .warnings(WithIncludes::Yes)
+            .iter()
+            .map(|warning| warning.ident())
+            .collect()
+    }
+
+    #[test]
+    fn event_handlers_unscheduled() {
+        let report = check(
+            r#"class Extended_PostInit_EventHandlers {
+    class test_main {
+        init = "sleep 1";
+    };
+};
+"#,
+        );
+        assert_eq!(codes(&report), vec!["SAW2"]);
+    }
+
+    #[test]
+    fn string_offsets() {
+        // characters before the string that are more than one byte
+        let source = r#"// Grüße
+class CfgVehicles {
+    class abe_unit {
+        class UserActions {
+            class Wave {
+                displayName = "Wäve";
+                statement = "player setVariable [""hi"", 1]; hint format ['%1 %2', player]";
+            };
+        };
+    };
+};
+"#;
+        let report = check(source);
+        assert_eq!(codes(&report), vec!["SAW6"]);
+        let warning = &report.warnings(WithIncludes::Yes)[0];
+        let diagnostic = warning.diagnostic().unwrap();
+        let label = diagnostic.labels[0].to_codespan();
+        assert_eq!(label.file_id.as_str(), "/config.cpp");
+        assert_eq!(&source[label.range], "'%1 %2'");
+    }
+
+    #[test]
+    fn eval_offsets() {
+        let source = "// Grüße\nclass CfgTest {\n    value = __EVAL(format ['%1 %2', 1]);\n};\n";
+        let report = check(source);
+        assert_eq!(codes(&report), vec!["SAW6"]);
+        let warning = &report.warnings(WithIncludes::Yes)[0];
+        let diagnostic = warning.diagnostic().unwrap();
+        assert_eq!(&source[diagnostic.labels[0].to_codespan().range], "'%1 %2'");
+    }
+}
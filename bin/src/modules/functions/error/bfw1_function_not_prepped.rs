@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use hemtt_common::{
+    position::Position,
+    reporting::{Code, Diagnostic, Label, Severity},
+};
+
+pub struct FunctionNotPrepped {
+    function: String,
+    position: Position,
+}
+
+impl Code for FunctionNotPrepped {
+    fn ident(&self) -> &'static str {
+        "BFW1"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!("function `{}` is never prepped", self.function)
+    }
+
+    fn label_message(&self) -> String {
+        String::from("referenced here")
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "add the function to the addon's `XEH_PREP.hpp`",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(
+            Diagnostic::simple(self).with_label(
+                Label::primary(self.position.path().clone(), self.position.span())
+                    .with_message(self.label_message()),
+            ),
+        )
+    }
+}
+
+impl FunctionNotPrepped {
+    pub fn code(function: String, position: Position) -> Arc<dyn Code> {
+        Arc::new(Self { function, position })
+    }
+}
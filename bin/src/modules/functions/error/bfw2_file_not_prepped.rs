@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use hemtt_common::{
+    reporting::{Code, Diagnostic, Severity},
+    workspace::WorkspacePath,
+};
+
+pub struct FileNotPrepped {
+    function: String,
+    file: WorkspacePath,
+}
+
+impl Code for FileNotPrepped {
+    fn ident(&self) -> &'static str {
+        "BFW2"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "function file {} is never prepped as `{}`",
+            self.file, self.function
+        )
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "add the function to the addon's `XEH_PREP.hpp`, or remove the file",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::simple(self))
+    }
+}
+
+impl FileNotPrepped {
+    pub fn code(function: String, file: WorkspacePath) -> Arc<dyn Code> {
+        Arc::new(Self { function, file })
+    }
+}
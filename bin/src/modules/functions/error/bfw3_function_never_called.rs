@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use hemtt_common::{
+    position::Position,
+    reporting::{Code, Diagnostic, Label, Severity},
+};
+
+pub struct FunctionNeverCalled {
+    function: String,
+    position: Position,
+}
+
+impl Code for FunctionNeverCalled {
+    fn ident(&self) -> &'static str {
+        "BFW3"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Help
+    }
+
+    fn message(&self) -> String {
+        format!("function `{}` is prepped but never used", self.function)
+    }
+
+    fn label_message(&self) -> String {
+        String::from("prepped here")
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(String::from(
+            "functions used only by other mods, or by name at runtime, can not be detected",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(
+            Diagnostic::simple(self).with_label(
+                Label::primary(self.position.path().clone(), self.position.span())
+                    .with_message(self.label_message()),
+            ),
+        )
+    }
+}
+
+impl FunctionNeverCalled {
+    pub fn code(function: String, position: Position) -> Arc<dyn Code> {
+        Arc::new(Self { function, position })
+    }
+}
@@ -0,0 +1,3 @@
+pub mod bfw1_function_not_prepped;
+pub mod bfw2_file_not_prepped;
+pub mod bfw3_function_never_called;
@@ -0,0 +1,362 @@
+//! Index of CBA style functions, connecting `fnc_*.sqf` files with `PREP` and `FUNC` usage
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
+
+use hemtt_common::{
+    position::Position,
+    reporting::{Mapping, Processed},
+    workspace::WorkspacePath,
+};
+use hemtt_sqf::{
+    analyze::{arity, codes::saw5_params_arity::ParamsArity, global_calls},
+    Statements,
+};
+
+use crate::report::Report;
+
+use self::error::{
+    bfw1_function_not_prepped::FunctionNotPrepped, bfw2_file_not_prepped::FileNotPrepped,
+    bfw3_function_never_called::FunctionNeverCalled,
+};
+
+mod error;
+
+/// Macros that compile a function
+const PREP_MACROS: &[&str] = &["PREP", "PREPMAIN", "PREP_RECOMPILE"];
+
+#[derive(Default)]
+pub struct FunctionIndex {
+    /// `fnc_*.sqf` files, with the folder of their addon and their name without the component
+    files: Vec<(String, String, WorkspacePath)>,
+    /// Where each function is prepped
+    prepped: HashMap<String, Vec<Position>>,
+    /// Where each function is referenced, by `FUNC`, `EFUNC`, `QFUNC`, or by name
+    references: HashMap<String, Vec<Position>>,
+    /// The required and total arguments of each `fnc_*.sqf` file, from its `params`
+    arities: HashMap<String, (usize, usize)>,
+    /// Calls of functions with a known number of arguments
+    calls: Vec<(String, usize, Position)>,
+}
+
+impl FunctionIndex {
+    /// Adds a file to the index if it is a function file, `fnc_*.sqf`
+    ///
+    /// `folder` is the folder of the addon the file is in, such as `addons/main`
+    pub fn add_file(&mut self, folder: &str, path: &WorkspacePath) {
+        let filename = path.filename().to_lowercase();
+        let Some(name) = filename
+            .strip_prefix("fnc_")
+            .and_then(|name| name.strip_suffix(".sqf"))
+        else {
+            return;
+        };
+        self.files
+            .push((folder.to_lowercase(), name.to_string(), path.clone()));
+    }
+
+    /// Finds every function name in the output of the preprocessor,
+    /// using the mappings to find the macro that created it
+    pub fn scan(&mut self, processed: &Processed) {
+        let output = processed.as_str();
+        let mut start = None;
+        for (offset, c) in output
+            .char_indices()
+            .chain(std::iter::once((output.len(), ' ')))
+        {
+            if is_ident(c) {
+                start.get_or_insert(offset);
+                continue;
+            }
+            let Some(word_start) = start.take() else {
+                continue;
+            };
+            let word = output[word_start..offset].to_lowercase();
+            if !word.contains("_fnc_") {
+                continue;
+            }
+            let Some(mapping) = processed.mapping(word_start) else {
+                continue;
+            };
+            let position = mapping.original().clone();
+            let sites = if mapping.was_macro()
+                && PREP_MACROS.contains(&mapping.token().to_source().as_str())
+            {
+                self.prepped.entry(word).or_default()
+            } else {
+                self.references.entry(word).or_default()
+            };
+            if !sites.contains(&position) {
+                sites.push(position);
+            }
+        }
+    }
+
+    /// Records the `params` of a function file, and the arguments passed to other functions
+    pub fn scan_sqf(
+        &mut self,
+        path: &WorkspacePath,
+        statements: &Statements,
+        processed: &Processed,
+    ) {
+        let filename = path.filename().to_lowercase();
+        if filename
+            .strip_prefix("fnc_")
+            .and_then(|name| name.strip_suffix(".sqf"))
+            .is_some()
+        {
+            if let Some(arity) = arity(statements) {
+                self.arities.insert(path.as_str().to_string(), arity);
+            }
+        }
+        for (function, given, span) in global_calls(statements) {
+            let function = function.to_lowercase();
+            if !function.contains("_fnc_") {
+                continue;
+            }
+            let Some(start) = processed.mapping(span.start) else {
+                continue;
+            };
+            // the whole call when it is in one file, otherwise where it starts
+            let position = processed
+                .mapping(span.end.saturating_sub(1))
+                .map(Mapping::original)
+                .filter(|end| end.path() == start.original().path())
+                .map_or_else(
+                    || start.original().clone(),
+                    |end| {
+                        Position::new(
+                            *start.original().start(),
+                            *end.end(),
+                            start.original().path().clone(),
+                        )
+                    },
+                );
+            self.calls.push((function, given, position));
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.files.extend(other.files);
+        self.arities.extend(other.arities);
+        self.calls.extend(other.calls);
+        for (name, sites) in other.prepped {
+            let entry = self.prepped.entry(name).or_default();
+            for site in sites {
+                if !entry.contains(&site) {
+                    entry.push(site);
+                }
+            }
+        }
+        for (name, sites) in other.references {
+            let entry = self.references.entry(name).or_default();
+            for site in sites {
+                if !entry.contains(&site) {
+                    entry.push(site);
+                }
+            }
+        }
+    }
+
+    /// Reports functions that are referenced but never prepped, files that are never prepped,
+    /// and functions that are prepped but never used
+    ///
+    /// The component of each addon, such as `abe_main`, is taken from the names its
+    /// `PREP` macros create, so it does not need to match the folder of the addon.
+    /// Only components that prep at least one function are checked
+    pub fn report(&self) -> Report {
+        let mut report = Report::new();
+        // the components prepped in each addon folder
+        let mut folders: HashMap<String, BTreeSet<&str>> = HashMap::new();
+        for (name, sites) in &self.prepped {
+            let Some((component, _)) = name.split_once("_fnc_") else {
+                continue;
+            };
+            for site in sites {
+                if let Some(folder) = site
+                    .path()
+                    .as_str()
+                    .trim_start_matches('/')
+                    .rsplit_once('/')
+                {
+                    folders
+                        .entry(folder.0.to_lowercase())
+                        .or_default()
+                        .insert(component);
+                }
+            }
+        }
+        let components = folders.values().flatten().copied().collect::<HashSet<_>>();
+        let uses_prep = |name: &str| {
+            name.split_once("_fnc_")
+                .is_some_and(|(component, _)| components.contains(component))
+        };
+
+        let mut references = self.references.iter().collect::<Vec<_>>();
+        references.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, sites) in references {
+            if !uses_prep(name) || self.prepped.contains_key(name) {
+                continue;
+            }
+            for site in sites {
+                report.warn(FunctionNotPrepped::code(name.clone(), site.clone()));
+            }
+        }
+
+        let mut arities: HashMap<String, (usize, usize)> = HashMap::new();
+        let mut files = self
+            .files
+            .iter()
+            .filter_map(|(folder, function, file)| {
+                // the components prepped in the addon, or in a folder inside it
+                let mut components = folders
+                    .iter()
+                    .filter(|(prepped, _)| {
+                        *prepped == folder
+                            || prepped
+                                .strip_prefix(folder.as_str())
+                                .is_some_and(|rest| rest.starts_with('/'))
+                    })
+                    .flat_map(|(_, components)| components.iter())
+                    .map(|component| format!("{component}_fnc_{function}"))
+                    .collect::<Vec<_>>();
+                components.sort();
+                components.dedup();
+                if let Some(arity) = self.arities.get(file.as_str()) {
+                    for name in &components {
+                        arities.insert(name.clone(), *arity);
+                    }
+                }
+                if components
+                    .iter()
+                    .any(|name| self.prepped.contains_key(name))
+                {
+                    return None;
+                }
+                Some((components.into_iter().next()?, file))
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, file) in files {
+            report.warn(FileNotPrepped::code(name, file.clone()));
+        }
+
+        let mut prepped = self.prepped.iter().collect::<Vec<_>>();
+        prepped.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, sites) in prepped {
+            if !uses_prep(name) || self.references.contains_key(name) {
+                continue;
+            }
+            if let Some(site) = sites.first() {
+                report.warn(FunctionNeverCalled::code(name.clone(), site.clone()));
+            }
+        }
+
+        // calls of functions in the same file are checked by the sqf analysis
+        for (function, given, position) in &self.calls {
+            let Some((required, total)) = arities.get(function) else {
+                continue;
+            };
+            if given < required {
+                report.warn(Arc::new(ParamsArity::at(
+                    position,
+                    function.clone(),
+                    *given,
+                    (*required, *total),
+                )));
+            }
+        }
+        report
+    }
+}
+
+const fn is_ident(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use hemtt_common::workspace::Workspace;
+    use hemtt_preprocessor::Processor;
+    use hemtt_sqf::parser::database::Database;
+
+    use crate::report::WithIncludes;
+
+    use super::FunctionIndex;
+
+    #[test]
+    fn component_from_prep() {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        for folder in ["addons", "addons/medical", "addons/medical/functions"] {
+            workspace.join(folder).unwrap().create_dir().unwrap();
+        }
+        let write = |path: &str, content: &str| {
+            let path = workspace.join(path).unwrap();
+            path.create_file()
+                .unwrap()
+                .write_all(content.as_bytes())
+                .unwrap();
+            path
+        };
+        // the component is `abe_medic`, not the folder name `medical`
+        write(
+            "addons/medical/script_component.hpp",
+            "#define PREP(name) abe_medic_fnc_##name = compile name\n",
+        );
+        let prep = write(
+            "addons/medical/XEH_PREP.hpp",
+            "#include \"script_component.hpp\"\nPREP(heal);\nPREP(unused);\n",
+        );
+        let init = write(
+            "addons/medical/XEH_postInit.sqf",
+            "[player] call abe_medic_fnc_heal;\n[] call abe_medic_fnc_missing;\n[] call cba_fnc_other;\n",
+        );
+        let mut index = FunctionIndex::default();
+        let mut sqf = vec![init];
+        for name in ["heal", "unused", "orphan"] {
+            let file = write(
+                &format!("addons/medical/functions/fnc_{name}.sqf"),
+                "params [\"_unit\", \"_amount\"];\n",
+            );
+            index.add_file("addons/medical", &file);
+            sqf.push(file);
+        }
+        index.scan(&Processor::run(&prep).unwrap());
+        for path in &sqf {
+            let processed = Processor::run(path).unwrap();
+            let statements = hemtt_sqf::parser::run(&Database::default(), &processed).unwrap();
+            index.scan(&processed);
+            index.scan_sqf(path, &statements, &processed);
+        }
+        let report = index.report();
+        let warnings = report
+            .warnings(WithIncludes::Yes)
+            .iter()
+            .map(|warning| (warning.ident(), warning.message()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![
+                (
+                    "BFW1",
+                    "function `abe_medic_fnc_missing` is never prepped".to_string()
+                ),
+                (
+                    "BFW2",
+                    "function file /addons/medical/functions/fnc_orphan.sqf is never prepped as `abe_medic_fnc_orphan`".to_string()
+                ),
+                (
+                    "BFW3",
+                    "function `abe_medic_fnc_unused` is prepped but never used".to_string()
+                ),
+                // calls in other files are checked against the `params` of the function
+                (
+                    "SAW5",
+                    "`abe_medic_fnc_heal` is called with 1 argument".to_string()
+                ),
+            ]
+        );
+    }
+}
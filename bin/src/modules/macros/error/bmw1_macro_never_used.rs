@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use hemtt_common::{
+    position::Position,
+    reporting::{Code, Diagnostic, Label, Severity},
+};
+
+pub struct MacroNeverUsed {
+    name: String,
+    position: Position,
+}
+
+impl Code for MacroNeverUsed {
+    fn ident(&self) -> &'static str {
+        "BMW1"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Help
+    }
+
+    fn message(&self) -> String {
+        format!("macro `{}` is defined but never used", self.name)
+    }
+
+    fn label_message(&self) -> String {
+        String::from("defined here")
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(String::from(
+            "only expansions and `#ifdef`, `#ifndef` or `#if` checks in files preprocessed by HEMTT count as uses, not those in skipped `#ifdef` branches, in files that are not preprocessed, or in other mods",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(
+            Diagnostic::simple(self).with_label(
+                Label::primary(self.position.path().clone(), self.position.span())
+                    .with_message(self.label_message()),
+            ),
+        )
+    }
+}
+
+impl MacroNeverUsed {
+    pub fn code(name: String, position: Position) -> Arc<dyn Code> {
+        Arc::new(Self { name, position })
+    }
+}
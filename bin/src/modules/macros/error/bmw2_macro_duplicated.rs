@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use hemtt_common::{
+    position::Position,
+    reporting::{Code, Diagnostic, Label, Severity},
+};
+
+pub struct MacroDuplicated {
+    name: String,
+    position: Position,
+    duplicates: Vec<Position>,
+}
+
+impl Code for MacroDuplicated {
+    fn ident(&self) -> &'static str {
+        "BMW2"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Help
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "macro `{}` is defined identically in {} headers",
+            self.name,
+            self.duplicates.len() + 1
+        )
+    }
+
+    fn label_message(&self) -> String {
+        String::from("defined here")
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "keep a single definition in a header that is included where the macro is needed",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        let mut diagnostic = Diagnostic::simple(self).with_label(
+            Label::primary(self.position.path().clone(), self.position.span())
+                .with_message(self.label_message()),
+        );
+        for duplicate in &self.duplicates {
+            diagnostic = diagnostic.with_label(
+                Label::secondary(duplicate.path().clone(), duplicate.span())
+                    .with_message("also defined here"),
+            );
+        }
+        Some(diagnostic)
+    }
+}
+
+impl MacroDuplicated {
+    pub fn code(name: String, position: Position, duplicates: Vec<Position>) -> Arc<dyn Code> {
+        Arc::new(Self {
+            name,
+            position,
+            duplicates,
+        })
+    }
+}
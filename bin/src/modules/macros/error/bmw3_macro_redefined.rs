@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use hemtt_common::{
+    position::Position,
+    reporting::{Code, Diagnostic, Label, Severity},
+};
+
+pub struct MacroRedefined {
+    name: String,
+    position: Position,
+    original: Position,
+}
+
+impl Code for MacroRedefined {
+    fn ident(&self) -> &'static str {
+        "BMW3"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "macro `{}` is redefined with a different body in another addon",
+            self.name
+        )
+    }
+
+    fn label_message(&self) -> String {
+        String::from("redefined here")
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(String::from(
+            "the body of the macro depends on which header was included last",
+        ))
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "give the macros different names, or define the macro in a single header",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(
+            Diagnostic::simple(self)
+                .with_label(
+                    Label::primary(self.position.path().clone(), self.position.span())
+                        .with_message(self.label_message()),
+                )
+                .with_label(
+                    Label::secondary(self.original.path().clone(), self.original.span())
+                        .with_message("first defined here"),
+                ),
+        )
+    }
+}
+
+impl MacroRedefined {
+    pub fn code(name: String, position: Position, original: Position) -> Arc<dyn Code> {
+        Arc::new(Self {
+            name,
+            position,
+            original,
+        })
+    }
+}
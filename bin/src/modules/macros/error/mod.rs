@@ -0,0 +1,3 @@
+pub mod bmw1_macro_never_used;
+pub mod bmw2_macro_duplicated;
+pub mod bmw3_macro_redefined;
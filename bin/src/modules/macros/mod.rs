@@ -0,0 +1,247 @@
+//! Index of macros defined in the project's headers, and where they are used
+
+use std::collections::{HashMap, HashSet};
+
+use hemtt_common::{
+    addons::Addon,
+    position::Position,
+    reporting::{is_ignored, MacroDefinition, Processed, Suppression},
+    workspace::WorkspacePath,
+};
+
+use crate::report::Report;
+
+use self::error::{
+    bmw1_macro_never_used::MacroNeverUsed, bmw2_macro_duplicated::MacroDuplicated,
+    bmw3_macro_redefined::MacroRedefined,
+};
+
+mod error;
+
+#[derive(Default)]
+pub struct MacroIndex {
+    /// Macros defined in project headers, by the position of their name
+    definitions: HashMap<Position, MacroDefinition>,
+    /// Definitions that were used at least once
+    used: HashSet<Position>,
+    /// Definitions that replaced another with a different body, and the definition they replaced
+    redefinitions: HashMap<Position, MacroDefinition>,
+    /// Codes suppressed by `#pragma hemtt suppress` in any of the files
+    suppressions: Vec<Suppression>,
+}
+
+impl MacroIndex {
+    /// Adds the macros defined and used while preprocessing a file
+    ///
+    /// Only macros defined in headers of the project are indexed,
+    /// not those defined in the file itself or in the include folder
+    pub fn scan(&mut self, root: &WorkspacePath, processed: &Processed) {
+        let mut headers = HashMap::new();
+        for definition in processed.definitions() {
+            let path = definition.position().path();
+            if path == root
+                || *headers
+                    .entry(path.clone())
+                    .or_insert_with(|| path.is_include())
+            {
+                continue;
+            }
+            if let Some(original) = definition.replaces().and_then(|original| {
+                processed
+                    .definitions()
+                    .iter()
+                    .find(|d| d.position() == original)
+            }) {
+                if original.body() != definition.body() {
+                    self.redefinitions
+                        .insert(definition.position().clone(), original.clone());
+                }
+            }
+            self.definitions
+                .entry(definition.position().clone())
+                .or_insert_with(|| definition.clone());
+        }
+        for (definition, usages) in processed.usage() {
+            if !usages.is_empty() {
+                self.used.insert(definition.clone());
+            }
+        }
+        for suppression in processed.suppressions() {
+            if !self.suppressions.contains(suppression) {
+                self.suppressions.push(suppression.clone());
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.definitions.extend(other.definitions);
+        self.used.extend(other.used);
+        self.redefinitions.extend(other.redefinitions);
+        for suppression in other.suppressions {
+            if !self.suppressions.contains(&suppression) {
+                self.suppressions.push(suppression);
+            }
+        }
+    }
+
+    /// Is a code suppressed at a definition, by `#pragma hemtt suppress` or `// hemtt-ignore`
+    fn is_suppressed(
+        &self,
+        code: &str,
+        position: &Position,
+        sources: &mut HashMap<WorkspacePath, String>,
+    ) -> bool {
+        let line = position.start().line() - 1;
+        if self
+            .suppressions
+            .iter()
+            .any(|s| s.covers(code, position.path(), line))
+        {
+            return true;
+        }
+        let content = sources
+            .entry(position.path().clone())
+            .or_insert_with(|| position.path().read_to_string().unwrap_or_default());
+        is_ignored(content, line, code)
+    }
+
+    /// Reports macros that are never used, macros defined identically in several headers,
+    /// and macros that are redefined with a different body in another addon
+    pub fn report(&self, addons: &[Addon]) -> Report {
+        let mut report = Report::new();
+        let mut sources = HashMap::new();
+        let mut definitions = self.definitions.values().collect::<Vec<_>>();
+        definitions.sort_by(|a, b| {
+            (a.position().path().as_str(), a.position().start())
+                .cmp(&(b.position().path().as_str(), b.position().start()))
+        });
+
+        for definition in &definitions {
+            if !self.used.contains(definition.position())
+                && !self.is_suppressed("BMW1", definition.position(), &mut sources)
+            {
+                report.warn(MacroNeverUsed::code(
+                    definition.name().to_string(),
+                    definition.position().clone(),
+                ));
+            }
+        }
+
+        let mut identical: HashMap<(&str, &str), Vec<&Position>> = HashMap::new();
+        for definition in &definitions {
+            if self.is_suppressed("BMW2", definition.position(), &mut sources) {
+                continue;
+            }
+            let positions = identical
+                .entry((definition.name(), definition.body()))
+                .or_default();
+            if !positions
+                .iter()
+                .any(|p| p.path() == definition.position().path())
+            {
+                positions.push(definition.position());
+            }
+        }
+        let mut identical = identical
+            .into_iter()
+            .filter(|(_, positions)| positions.len() > 1)
+            .collect::<Vec<_>>();
+        identical.sort_by(|a, b| a.0.cmp(&b.0));
+        for ((name, _), positions) in identical {
+            report.warn(MacroDuplicated::code(
+                name.to_string(),
+                positions[0].clone(),
+                positions[1..].iter().map(|p| (*p).clone()).collect(),
+            ));
+        }
+
+        for definition in &definitions {
+            let Some(original) = self.redefinitions.get(definition.position()) else {
+                continue;
+            };
+            if addon_of(definition.position(), addons) != addon_of(original.position(), addons)
+                && !self.is_suppressed("BMW3", definition.position(), &mut sources)
+            {
+                report.warn(MacroRedefined::code(
+                    definition.name().to_string(),
+                    definition.position().clone(),
+                    original.position().clone(),
+                ));
+            }
+        }
+        report
+    }
+}
+
+/// The name of the addon that contains a position
+fn addon_of<'a>(position: &Position, addons: &'a [Addon]) -> Option<&'a str> {
+    let path = position.path().as_str();
+    addons
+        .iter()
+        .find(|addon| path.starts_with(&format!("/{}/", addon.folder())))
+        .map(Addon::name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use hemtt_common::{
+        addons::Addon,
+        workspace::{LayerType, Workspace},
+    };
+    use hemtt_preprocessor::Processor;
+
+    use crate::report::WithIncludes;
+
+    use super::MacroIndex;
+
+    #[test]
+    fn report() {
+        let root = Path::new("tests/macros");
+        let addons = Addon::scan(root).unwrap();
+        let workspace = Workspace::builder()
+            .physical(&root.canonicalize().unwrap(), LayerType::Source)
+            .finish(None)
+            .unwrap();
+        let mut index = MacroIndex::default();
+        for file in [
+            "addons/main/XEH_postInit.sqf",
+            "addons/other/XEH_postInit.sqf",
+        ] {
+            let path = workspace.join(file).unwrap();
+            let mut file_index = MacroIndex::default();
+            file_index.scan(&path, &Processor::run(&path).unwrap());
+            index.merge(file_index);
+        }
+        let report = index.report(&addons);
+        let warnings = report
+            .warnings(WithIncludes::Yes)
+            .iter()
+            .map(|warning| (warning.ident(), warning.message()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![
+                // `CHECKED` is used by `#ifdef`, `INACTIVE` only in a branch that is skipped
+                (
+                    "BMW1",
+                    "macro `NEVER_USED` is defined but never used".to_string()
+                ),
+                (
+                    "BMW1",
+                    "macro `INACTIVE` is defined but never used".to_string()
+                ),
+                (
+                    "BMW2",
+                    "macro `SHARED` is defined identically in 2 headers".to_string()
+                ),
+                (
+                    "BMW3",
+                    "macro `DEBUG_MODE` is redefined with a different body in another addon"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -10,12 +10,16 @@ pub mod pbo;
 pub use hook::Hooks;
 
 mod binarize;
+pub(crate) mod config_sqf;
 mod file_patching;
 mod files;
+mod functions;
+mod macros;
 mod new;
 mod rapifier;
+mod remote_exec;
 pub(crate) mod sign;
-mod sqf;
+pub(crate) mod sqf;
 
 pub use binarize::Binarize;
 pub use file_patching::FilePatching;
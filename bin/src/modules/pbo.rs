@@ -1,5 +1,7 @@
 use std::{
+    collections::HashMap,
     fs::{create_dir_all, File},
+    io::Cursor,
     sync::atomic::{AtomicU16, Ordering},
 };
 
@@ -8,10 +10,12 @@ use hemtt_common::{
     addons::{Addon, Location},
     prefix::{Prefix, FILES},
     version::Version,
+    workspace::WorkspacePath,
 };
 use hemtt_pbo::WritablePbo;
+use hemtt_preprocessor::Processor;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
-use vfs::VfsFileType;
+use vfs::{SeekAndRead, VfsFileType};
 
 use crate::{context::Context, error::Error, report::Report};
 
@@ -63,7 +67,7 @@ fn _build(
     version: &Version,
     git_hash: Option<&String>,
 ) -> Result<(), Error> {
-    let mut pbo = WritablePbo::new();
+    let mut pbo = WritablePbo::<Box<dyn SeekAndRead + Send>>::new();
     let target = ctx.build_folder();
 
     let pbo_name = addon.pbo_name(ctx.config().prefix());
@@ -101,6 +105,12 @@ fn _build(
         target_pbo.display()
     );
 
+    // the game does not know the project's defines or HEMTT's builtins,
+    // so SQF that uses them is packed after HEMTT preprocessed it
+    let project_defines = !ctx.defines(addon).is_empty();
+    let defines = ctx.defines_with_builtins(addon)?;
+    let minify = ctx.folder() == "release" && ctx.config().hemtt().release().minify();
+
     pbo.add_property("hemtt", env!("HEMTT_VERSION"));
     pbo.add_property("version", version.to_string());
 
@@ -146,6 +156,10 @@ fn _build(
                 .trim_start_matches(&format!("/{}/", addon.folder()))
                 .replace('/', "\\");
             trace!("adding file {:?}", file);
+            if let Some(packed) = packed_sqf(ctx, &entry, &defines, project_defines, minify)? {
+                pbo.add_file(file, Box::new(Cursor::new(packed.into_bytes())))?;
+                continue;
+            }
             pbo.add_file(file, entry.open_file()?)?;
         }
     }
@@ -160,3 +174,32 @@ fn _build(
     pbo.write(&mut File::create(target_pbo)?, true)?;
     Ok(())
 }
+
+/// The text of an SQF file to pack, if it differs from the source as written
+///
+/// SQF that uses defines the game does not know is packed preprocessed,
+/// and is minified after it was checked when `minify` is set
+fn packed_sqf(
+    ctx: &Context,
+    entry: &WorkspacePath,
+    defines: &HashMap<String, String>,
+    project_defines: bool,
+    minify: bool,
+) -> Result<Option<String>, Error> {
+    if entry.extension().as_deref() != Some("sqf") || entry.filename().ends_with(".inc.sqf") {
+        return Ok(None);
+    }
+    let processed = Processor::run_with_defines(entry, defines, ctx.preprocessor_cache())?;
+    let text = if project_defines || super::sqf::uses_builtins(&processed) {
+        super::sqf::packed(entry, &processed)
+    } else if minify {
+        entry.read_to_string()?
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(if minify {
+        hemtt_sqf::minify::minify(&text)
+    } else {
+        text
+    }))
+}
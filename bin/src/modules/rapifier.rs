@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     path::PathBuf,
     sync::atomic::{AtomicU16, Ordering},
 };
@@ -6,12 +7,13 @@ use std::{
 use hemtt_common::{addons::Addon, workspace::WorkspacePath};
 use hemtt_config::{parse, rapify::Rapify};
 use hemtt_preprocessor::Processor;
+use hemtt_sqf::parser::database::Database;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use vfs::VfsFileType;
 
 use crate::{context::Context, error::Error, report::Report};
 
-use super::Module;
+use super::{config_sqf, sqf::database, Module};
 
 // type RapifyResult = (Vec<(String, Vec<Annotation>)>, Result<(), Error>);
 
@@ -55,6 +57,10 @@ impl Module for Rapifier {
                             debug!("skipping {}", entry.as_str());
                             continue;
                         }
+                        if !ctx.is_selected(&entry) {
+                            trace!("unchanged {}", entry.as_str());
+                            continue;
+                        }
                         entries.push((addon, entry));
                     }
                 }
@@ -62,10 +68,17 @@ impl Module for Rapifier {
             })
             .collect::<Result<Vec<_>, Error>>()?;
 
+        let database = database(ctx.config(), ctx.project_folder())?;
+        let mut defines = HashMap::new();
+        for (addon, _) in &entries {
+            if !defines.contains_key(addon.name()) {
+                defines.insert(addon.name(), ctx.defines_with_builtins(addon)?);
+            }
+        }
         let reports = entries
             .par_iter()
             .map(|(addon, entry)| {
-                let report = rapify(addon, entry, ctx)?;
+                let report = rapify(addon, entry, ctx, &defines[addon.name()], &database)?;
                 counter.fetch_add(1, Ordering::Relaxed);
                 Ok(report)
             })
@@ -81,9 +94,15 @@ impl Module for Rapifier {
 }
 
 #[allow(clippy::too_many_lines)]
-pub fn rapify(addon: &Addon, path: &WorkspacePath, ctx: &Context) -> Result<Report, Error> {
+pub fn rapify(
+    addon: &Addon,
+    path: &WorkspacePath,
+    ctx: &Context,
+    defines: &HashMap<String, String>,
+    database: &Database,
+) -> Result<Report, Error> {
     let mut report = Report::new();
-    let processed = match Processor::run(path) {
+    let processed = match Processor::run_with_defines(path, defines, ctx.preprocessor_cache()) {
         Ok(processed) => processed,
         Err(hemtt_preprocessor::Error::Code(e)) => {
             report.error(e);
@@ -93,6 +112,7 @@ pub fn rapify(addon: &Addon, path: &WorkspacePath, ctx: &Context) -> Result<Repo
             return Err(e.into());
         }
     };
+    ctx.include_graph().record(path, &processed);
     for warning in processed.warnings() {
         report.warn(warning.clone());
     }
@@ -114,6 +134,13 @@ pub fn rapify(addon: &Addon, path: &WorkspacePath, ctx: &Context) -> Result<Repo
     if !configreport.errors().is_empty() {
         return Ok(report);
     }
+    report.merge(config_sqf::check(
+        configreport.config(),
+        &processed,
+        ctx.config(),
+        Some(addon),
+        database,
+    ));
     let out = if path.filename().to_lowercase() == "config.cpp" {
         let (version, cfgpatch) = configreport.required_version();
         let mut file = path;
@@ -0,0 +1,133 @@
+//! Collects the targets of `remoteExec` and `remoteExecCall`, to suggest a `CfgRemoteExec` whitelist
+
+use std::collections::BTreeMap;
+
+use hemtt_sqf::{analyze::remote_exec::remote_execs, parser::database::Database, Statements};
+
+#[derive(Default)]
+pub struct RemoteExecIndex {
+    /// Functions that are remotely executed, by lowercase name
+    functions: BTreeMap<String, Target>,
+    /// Commands that are remotely executed, by lowercase name
+    commands: BTreeMap<String, Target>,
+}
+
+struct Target {
+    /// The name as it was first written
+    name: String,
+    /// Whether every use only targets the server
+    server_only: bool,
+}
+
+impl RemoteExecIndex {
+    /// Adds every target with a known name, a command if it is known to the database,
+    /// otherwise a function
+    pub fn scan(&mut self, statements: &Statements, database: &Database) {
+        for remote_exec in remote_execs(statements) {
+            let Some(name) = remote_exec.target else {
+                continue;
+            };
+            let targets = if database.has_command(name) {
+                &mut self.commands
+            } else {
+                &mut self.functions
+            };
+            targets
+                .entry(name.to_lowercase())
+                .and_modify(|target| target.server_only &= remote_exec.server_only)
+                .or_insert_with(|| Target {
+                    name: name.to_string(),
+                    server_only: remote_exec.server_only,
+                });
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        for (targets, other) in [
+            (&mut self.functions, other.functions),
+            (&mut self.commands, other.commands),
+        ] {
+            for (key, target) in other {
+                targets
+                    .entry(key)
+                    .and_modify(|existing| existing.server_only &= target.server_only)
+                    .or_insert(target);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.commands.is_empty()
+    }
+
+    /// A `CfgRemoteExec` that only allows the targets that were found
+    pub fn config(&self) -> String {
+        let mut lines = vec![String::from("class CfgRemoteExec {")];
+        for (class, targets) in [("Functions", &self.functions), ("Commands", &self.commands)] {
+            lines.push(format!("    class {class} {{"));
+            lines.push(String::from("        mode = 1;"));
+            for target in targets.values() {
+                // allowedTargets: 0 = anywhere, 2 = server only
+                lines.push(format!(
+                    "        class {} {{ allowedTargets = {}; }};",
+                    target.name,
+                    if target.server_only { 2 } else { 0 }
+                ));
+            }
+            lines.push(String::from("    };"));
+        }
+        lines.push(String::from("};\n"));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hemtt_common::workspace::Workspace;
+    use hemtt_preprocessor::Processor;
+    use hemtt_sqf::parser::database::Database;
+
+    use super::RemoteExecIndex;
+
+    fn index(source: &str) -> RemoteExecIndex {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let path = workspace.join("test.sqf").unwrap();
+        path.create_file()
+            .unwrap()
+            .write_all(source.as_bytes())
+            .unwrap();
+        let database = Database::default();
+        let processed = Processor::run(&path).unwrap();
+        let statements = hemtt_sqf::parser::run(&database, &processed).unwrap();
+        let mut index = RemoteExecIndex::default();
+        index.scan(&statements, &database);
+        index
+    }
+
+    #[test]
+    fn config() {
+        let mut remote_exec = index(
+            "[_unit] remoteExec [\"abe_main_fnc_sync\", 2];\n[\"hello\"] remoteExecCall [\"hint\", 0];\n[] remoteExec [_dynamic, 0];\n",
+        );
+        // a use from another file that is not limited to the server
+        remote_exec.merge(index(
+            "[] remoteExecCall [\"ABE_main_fnc_sync\"];\n[] remoteExec [\"abe_main_fnc_save\", 2];\n",
+        ));
+        assert!(!remote_exec.is_empty());
+        assert_eq!(
+            remote_exec.config(),
+            r"class CfgRemoteExec {
+    class Functions {
+        mode = 1;
+        class abe_main_fnc_save { allowedTargets = 2; };
+        class abe_main_fnc_sync { allowedTargets = 0; };
+    };
+    class Commands {
+        mode = 1;
+        class hint { allowedTargets = 0; };
+    };
+};
+"
+        );
+    }
+}
@@ -1,5 +1,14 @@
-use std::sync::atomic::{AtomicU16, Ordering};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::atomic::{AtomicU16, Ordering},
+};
 
+use hemtt_common::{
+    project::{sqf::Command, ProjectConfig},
+    reporting::Processed,
+    workspace::WorkspacePath,
+};
 use hemtt_preprocessor::Processor;
 use hemtt_sqf::{
     analyze::analyze,
@@ -9,7 +18,7 @@ use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{context::Context, error::Error, report::Report};
 
-use super::Module;
+use super::{functions::FunctionIndex, macros::MacroIndex, remote_exec::RemoteExecIndex, Module};
 
 #[derive(Default)]
 pub struct SQFCompiler;
@@ -31,22 +40,43 @@ impl Module for SQFCompiler {
                     if entry.extension() != sqf_ext || entry.filename().ends_with(".inc.sqf") {
                         continue;
                     }
+                    if !ctx.is_selected(&entry) {
+                        trace!("unchanged {}", entry);
+                        continue;
+                    }
                     entries.push((addon, entry));
                 }
             }
         }
-        let database = Database::default();
+        let database = database(ctx.config(), ctx.project_folder())?;
+        let defines = ctx
+            .addons()
+            .iter()
+            .map(|addon| Ok((addon.name(), ctx.defines_with_builtins(addon)?)))
+            .collect::<Result<HashMap<_, _>, Error>>()?;
         let reports = entries
             .par_iter()
             .map(|(addon, entry)| {
                 trace!("asc compiling {}", entry);
                 let mut report = Report::new();
-                let processed = Processor::run(entry)?;
+                let processed = Processor::run_with_defines(
+                    entry,
+                    &defines[addon.name()],
+                    ctx.preprocessor_cache(),
+                )?;
+                ctx.include_graph().record(entry, &processed);
                 for warning in processed.warnings() {
                     report.warn(warning.clone());
                 }
+                let mut functions = FunctionIndex::default();
+                functions.scan(&processed);
+                let mut macros = MacroIndex::default();
+                macros.scan(entry, &processed);
+                let mut remote_exec = RemoteExecIndex::default();
                 match hemtt_sqf::parser::run(&database, &processed) {
                     Ok(sqf) => {
+                        remote_exec.scan(&sqf, &database);
+                        functions.scan_sqf(entry, &sqf, &processed);
                         // let mut out = entry.with_extension("sqfc")?.create_file()?;
                         let (warnings, errors) =
                             analyze(&sqf, Some(ctx.config()), &processed, Some(addon), &database);
@@ -60,9 +90,9 @@ impl Module for SQFCompiler {
                         for error in errors {
                             report.error(error);
                         }
-                        Ok(report)
+                        Ok((report, functions, macros, remote_exec))
                     }
-                    Err(ParserError::ParsingError(e)) => {
+                    Err(ParserError::ParsingError(e, partial)) => {
                         if processed.as_str().starts_with("force ")
                             || processed.as_str().contains("\nforce ")
                         {
@@ -71,22 +101,183 @@ impl Module for SQFCompiler {
                             for error in e {
                                 report.error(error);
                             }
+                            if let Some(sqf) = partial {
+                                remote_exec.scan(&sqf, &database);
+                                functions.scan_sqf(entry, &sqf, &processed);
+                                let (warnings, errors) = analyze(
+                                    &sqf,
+                                    Some(ctx.config()),
+                                    &processed,
+                                    Some(addon),
+                                    &database,
+                                );
+                                for warning in warnings {
+                                    report.warn(warning);
+                                }
+                                for error in errors {
+                                    report.error(error);
+                                }
+                            }
                         }
-                        Ok(report)
+                        Ok((report, functions, macros, remote_exec))
                     }
                     Err(ParserError::LexingError(e)) => {
                         for error in e {
                             report.error(error);
                         }
-                        Ok(report)
+                        Ok((report, functions, macros, remote_exec))
                     }
                 }
             })
-            .collect::<Result<Vec<Report>, Error>>()?;
-        for new_report in reports {
+            .collect::<Result<Vec<(Report, FunctionIndex, MacroIndex, RemoteExecIndex)>, Error>>(
+            )?;
+        let mut functions = FunctionIndex::default();
+        let mut macros = MacroIndex::default();
+        let mut remote_exec = RemoteExecIndex::default();
+        for (new_report, new_functions, new_macros, new_remote_exec) in reports {
             report.merge(new_report);
+            functions.merge(new_functions);
+            macros.merge(new_macros);
+            remote_exec.merge(new_remote_exec);
+        }
+        for (addon, entry) in &entries {
+            functions.add_file(&addon.folder(), entry);
+        }
+        // functions, macros and remote exec can only be checked against every file of the project
+        if ctx.is_partial() {
+            debug!("skipping project wide checks, only changed files are checked");
+        } else {
+            // functions and macros are often referenced from config, such as in event handlers
+            for addon in ctx.addons() {
+                let config = ctx.workspace().join(addon.folder())?.join("config.cpp")?;
+                if config.exists()? {
+                    if let Ok(processed) = Processor::run_with_defines(
+                        &config,
+                        &defines[addon.name()],
+                        ctx.preprocessor_cache(),
+                    ) {
+                        functions.scan(&processed);
+                        macros.scan(&config, &processed);
+                    }
+                }
+            }
+            report.merge(functions.report());
+            report.merge(macros.report(ctx.addons()));
+            if !remote_exec.is_empty() {
+                let path = ctx.out_folder().join("CfgRemoteExec.hpp");
+                std::fs::write(&path, remote_exec.config())?;
+                info!("Suggested CfgRemoteExec written to {}", path.display());
+            }
         }
         info!("Validated {} sqf files", counter.load(Ordering::Relaxed));
         Ok(report)
     }
 }
+
+/// Creates the command database, including any commands declared by the project
+///
+/// # Errors
+/// [`Error::Io`] if the commands file can not be read
+/// [`Error::SerdeJson`] if the commands file is not valid
+pub fn database(config: &ProjectConfig, project_folder: &Path) -> Result<Database, Error> {
+    let mut database = Database::default();
+    for command in config.sqf().commands() {
+        database.add_custom_command(command);
+    }
+    if let Some(file) = config.sqf().commands_file() {
+        let commands: Vec<Command> =
+            serde_json::from_str(&std::fs::read_to_string(project_folder.join(file))?)?;
+        for command in &commands {
+            command.validate()?;
+            database.add_custom_command(command);
+        }
+    }
+    Ok(database)
+}
+
+/// SQF as it is packed into a PBO, after HEMTT preprocessed it with the defines of the project
+///
+/// Each line of output is moved back to the line of the file it came from,
+/// so line numbers reported by script errors still point at the original line.
+/// Output from an include, such as a macro defined by `script_component.hpp`,
+/// is joined onto the line before it.
+pub fn packed(path: &WorkspacePath, processed: &Processed) -> String {
+    let mut lines = vec![String::new()];
+    let mut current = 0;
+    let mut offset = 0;
+    for line in processed.as_str().split_inclusive('\n') {
+        let start = offset + (line.len() - line.trim_start().len());
+        offset += line.len();
+        if line.trim().is_empty() {
+            continue;
+        }
+        // the tokens around a line can come from the line before it, or from a macro used on it
+        if let Some(line) = processed
+            .mappings(start)
+            .iter()
+            .map(|mapping| mapping.original())
+            .filter(|original| original.path() == path)
+            .map(|original| original.start().line())
+            .max()
+        {
+            // lines are never moved up, so the order of the output is kept
+            current = current.max(line.saturating_sub(1));
+        }
+        if lines.len() <= current {
+            lines.resize(current + 1, String::new());
+        }
+        if lines[current].is_empty() {
+            lines[current].push_str(line.trim_end());
+        } else {
+            lines[current].push(' ');
+            lines[current].push_str(line.trim());
+        }
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Does a file, or a file it includes, use one of HEMTT's builtin macros, such as `__HEMTT_PROJECT_VERSION__`
+pub fn uses_builtins(processed: &Processed) -> bool {
+    (0..)
+        .map_while(|index| processed.source(index))
+        .any(|(_, source)| source.contains("__HEMTT_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, path::Path, sync::Arc};
+
+    use hemtt_preprocessor::{Cache, Processor};
+
+    use crate::utils::standalone;
+
+    use super::packed;
+
+    #[test]
+    fn packed_lines() {
+        let path = standalone::workspace(Path::new("tests/pack/fnc_heal.sqf"), &[]).unwrap();
+        let defines = HashMap::from([("DEBUG_MODE_FULL".to_string(), String::new())]);
+        let processed =
+            Processor::run_with_defines(&path, &defines, &Arc::new(Cache::new())).unwrap();
+        let source = std::fs::read_to_string("tests/pack/fnc_heal.sqf")
+            .unwrap()
+            .replace('\r', "");
+        let output = packed(&path, &processed);
+        let expected = std::fs::read_to_string("tests/pack/packed.sqf")
+            .unwrap()
+            .replace('\r', "");
+        if expected.is_empty() {
+            std::fs::write("tests/pack/packed.sqf", &output).unwrap();
+        }
+        assert_eq!(output, expected);
+        assert_eq!(output.lines().count(), source.lines().count());
+        // debug code is left out without the define
+        let release = packed(
+            &path,
+            &Processor::run_with_defines(&path, &HashMap::new(), &Arc::new(Cache::new())).unwrap(),
+        );
+        assert!(!release.contains("diag_log"));
+    }
+}
@@ -4,7 +4,11 @@ use std::{
     sync::Arc,
 };
 
-use hemtt_common::reporting::{Code, WorkspaceFiles};
+use hemtt_common::{
+    addons::Addon,
+    project::lints::{self, Level},
+    reporting::{Code, Diagnostic, Fix, ReportFormat, Severity, Token, WorkspaceFiles},
+};
 
 use crate::Error;
 
@@ -25,9 +29,14 @@ impl Report {
 
     /// Write the report to the `ci_annotations.txt` file for GitHub Actions
     ///
+    /// Nothing is written outside of a project, such as when running `hemtt utils`
+    ///
     /// # Errors
     /// [`std::io::Error`] if the file cannot be opened
     pub fn write_ci_annotations(&self) -> Result<(), Error> {
+        if !std::path::Path::new(".hemttout").exists() {
+            return Ok(());
+        }
         trace!("writing ci annotations to .hemttout/ci_annotations.txt");
         let mut ci_annotation = BufWriter::new(
             OpenOptions::new()
@@ -54,6 +63,39 @@ impl Report {
         Ok(())
     }
 
+    /// Write the report to `.hemttout` in each of the formats
+    ///
+    /// Nothing is written outside of a project, such as when running `hemtt utils`
+    ///
+    /// # Errors
+    /// [`std::io::Error`] if a file cannot be written
+    pub fn write_formats(&self, formats: &[ReportFormat]) -> Result<(), Error> {
+        let out = std::path::Path::new(".hemttout");
+        if formats.is_empty() || !out.exists() {
+            return Ok(());
+        }
+        let workspace_files = WorkspaceFiles::new();
+        let diagnostics = self
+            .warnings(WithIncludes::No)
+            .iter()
+            .chain(self.errors(WithIncludes::No).iter())
+            .filter_map(|code| code.diagnostic())
+            .collect::<Vec<_>>();
+        for format in formats {
+            let path = out.join(format.file_name());
+            trace!("writing {} report to {}", format, path.display());
+            std::fs::write(
+                &path,
+                serde_json::to_string_pretty(&format.render(
+                    &diagnostics,
+                    &workspace_files,
+                    env!("HEMTT_VERSION"),
+                ))?,
+            )?;
+        }
+        Ok(())
+    }
+
     pub fn write_to_stdout(&self) {
         let workspace_files = WorkspaceFiles::new();
         for code in self
@@ -98,6 +140,45 @@ impl Report {
         filter_codes(&self.errors, includes)
     }
 
+    #[must_use]
+    /// Applies the levels from the `[lints]` table of project.toml to the warnings
+    ///
+    /// Levels set for an addon glob override the level set for the project.
+    /// If more than one glob matches, the strictest level is used.
+    /// Errors are always reported as errors, a level can not be set for them,
+    /// see [`lints::Options::validate`].
+    pub fn with_lints(self, options: &lints::Options, addons: &[Addon]) -> Self {
+        let mut report = Self {
+            warnings: Vec::with_capacity(self.warnings.len()),
+            errors: self.errors,
+        };
+        for warning in self.warnings {
+            let addon = addon_of(warning.as_ref(), addons);
+            let level = addon
+                .and_then(|addon| {
+                    options
+                        .addons()
+                        .iter()
+                        .filter(|(pattern, _)| {
+                            glob::Pattern::new(pattern)
+                                .is_ok_and(|pattern| pattern.matches(addon.name()))
+                        })
+                        .filter_map(|(_, codes)| lints::level(codes, warning.ident()))
+                        .max()
+                })
+                .or_else(|| options.level(warning.ident()));
+            match level {
+                None => report.warnings.push(warning),
+                Some(Level::Allow) => {}
+                Some(Level::Warn) => report
+                    .warnings
+                    .push(Leveled::wrap(warning, Severity::Warning)),
+                Some(Level::Deny) => report.errors.push(Leveled::wrap(warning, Severity::Error)),
+            }
+        }
+        report
+    }
+
     #[must_use]
     /// Returns `true` if there are any errors
     pub fn failed(&self) -> bool {
@@ -105,6 +186,74 @@ impl Report {
     }
 }
 
+/// The addon that contains the primary label of a code
+fn addon_of<'a>(code: &dyn Code, addons: &'a [Addon]) -> Option<&'a Addon> {
+    let diagnostic = code.diagnostic()?;
+    let path = diagnostic.labels.first()?.file().as_str().to_string();
+    addons
+        .iter()
+        .find(|addon| path.starts_with(&format!("/{}/", addon.folder())))
+}
+
+/// A code reported at a different severity, set by the `[lints]` table
+struct Leveled {
+    code: Arc<dyn Code>,
+    severity: Severity,
+}
+
+impl Leveled {
+    fn wrap(code: Arc<dyn Code>, severity: Severity) -> Arc<dyn Code> {
+        if code.severity() == severity {
+            return code;
+        }
+        Arc::new(Self { code, severity })
+    }
+}
+
+impl Code for Leveled {
+    fn token(&self) -> Option<&Token> {
+        self.code.token()
+    }
+
+    fn ident(&self) -> &'static str {
+        self.code.ident()
+    }
+
+    fn message(&self) -> String {
+        self.code.message()
+    }
+
+    fn label_message(&self) -> String {
+        self.code.label_message()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn help(&self) -> Option<String> {
+        self.code.help()
+    }
+
+    fn note(&self) -> Option<String> {
+        self.code.note()
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        self.code.suggestion()
+    }
+
+    fn fix(&self) -> Option<Fix> {
+        self.code.fix()
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.code
+            .diagnostic()
+            .map(|diagnostic| diagnostic.set_severity(self.severity))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WithIncludes {
     Yes,
@@ -128,3 +277,61 @@ fn filter_codes(codes: &[Arc<dyn Code>], includes: WithIncludes) -> Vec<Arc<dyn
         .cloned()
         .collect::<Vec<_>>()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use hemtt_common::{
+        project::lints,
+        reporting::{Code, Fix, Severity},
+        workspace::{Workspace, WorkspacePath},
+    };
+
+    use super::{Report, WithIncludes};
+
+    struct Fixable {
+        ident: &'static str,
+        path: WorkspacePath,
+    }
+
+    impl Code for Fixable {
+        fn ident(&self) -> &'static str {
+            self.ident
+        }
+
+        fn message(&self) -> String {
+            String::from("can be fixed")
+        }
+
+        fn severity(&self) -> Severity {
+            Severity::Help
+        }
+
+        fn fix(&self) -> Option<Fix> {
+            Some(Fix::new(self.path.clone(), 0..1, String::new()))
+        }
+    }
+
+    #[test]
+    fn leveled_fix() {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let path = workspace.join("test.sqf").unwrap();
+        let options: lints::Options = toml::from_str("TC1 = \"warn\"\nTC2 = \"deny\"\n").unwrap();
+        let mut report = Report::new();
+        for ident in ["TC1", "TC2"] {
+            report.warn(Arc::new(Fixable {
+                ident,
+                path: path.clone(),
+            }));
+        }
+        let report = report.with_lints(&options, &[]);
+        let expected = Some(Fix::new(path, 0..1, String::new()));
+        let warnings = report.warnings(WithIncludes::Yes);
+        assert_eq!(warnings[0].severity(), Severity::Warning);
+        assert_eq!(warnings[0].fix(), expected);
+        let errors = report.errors(WithIncludes::Yes);
+        assert_eq!(errors[0].severity(), Severity::Error);
+        assert_eq!(errors[0].fix(), expected);
+    }
+}
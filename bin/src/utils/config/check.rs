@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+
+use clap::{ArgMatches, Command};
+use hemtt_config::parse;
+use hemtt_preprocessor::Processor;
+
+use crate::{modules::config_sqf, report::Report, utils::standalone, Error};
+
+#[must_use]
+pub fn cli() -> Command {
+    Command::new("check")
+        .about("Check config files outside of a project")
+        .long_about(
+            "Preprocess and parse config files, such as description.ext, without a HEMTT project",
+        )
+        .arg(standalone::files_arg())
+        .arg(standalone::include_arg())
+}
+
+/// Execute the check command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(matches: &ArgMatches) -> Result<Report, Error> {
+    let (files, includes) = standalone::args(matches);
+    check(&std::env::current_dir()?, &files, &includes)
+}
+
+/// Check config files, with the project in `folder` if there is one
+fn check(folder: &Path, files: &[PathBuf], includes: &[PathBuf]) -> Result<Report, Error> {
+    let (project, database) = standalone::project(folder)?;
+    let mut report = Report::new();
+    for file in files {
+        let path = standalone::workspace(file, includes)?;
+        let processed = match Processor::run(&path) {
+            Ok(processed) => processed,
+            Err(hemtt_preprocessor::Error::Code(e)) => {
+                report.error(e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        for warning in processed.warnings() {
+            report.warn(warning.clone());
+        }
+        match parse(project.as_ref(), &processed) {
+            Ok(config) => {
+                report.add_warnings(config.warnings().to_vec());
+                report.add_errors(config.errors().to_vec());
+                if let (Some(project), true) = (&project, config.errors().is_empty()) {
+                    report.merge(config_sqf::check(
+                        config.config(),
+                        &processed,
+                        project,
+                        None,
+                        &database,
+                    ));
+                }
+            }
+            Err(errors) => report.add_errors(errors),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::report::WithIncludes;
+
+    use super::check;
+
+    #[test]
+    fn project_sqf() {
+        let files = [PathBuf::from("tests/standalone/mission/description.ext")];
+        let includes = [PathBuf::from("tests/standalone/include")];
+        // the SQF in the config is only checked inside a project, with its commands
+        let report = check(Path::new("tests/standalone"), &files, &includes).unwrap();
+        let errors = report
+            .errors(WithIncludes::Yes)
+            .iter()
+            .map(|error| error.message())
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec!["unparseable syntax".to_string()]);
+        let report = check(Path::new("tests/standalone/mission"), &files, &includes).unwrap();
+        assert!(report.errors(WithIncludes::Yes).is_empty());
+    }
+}
@@ -0,0 +1,24 @@
+mod check;
+
+use clap::{ArgMatches, Command};
+
+use crate::{report::Report, Error};
+
+#[must_use]
+pub fn cli() -> Command {
+    Command::new("config")
+        .about("Commands for config files")
+        .arg_required_else_help(true)
+        .subcommand(check::cli())
+}
+
+/// Execute the config command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(matches: &ArgMatches) -> Result<Report, Error> {
+    match matches.subcommand() {
+        Some(("check", matches)) => check::execute(matches),
+        _ => unreachable!(),
+    }
+}
@@ -1,4 +1,8 @@
+pub mod config;
 pub mod inspect;
 pub mod paa;
 pub mod pbo;
+pub mod preprocess;
+pub mod sqf;
+pub mod standalone;
 pub mod verify;
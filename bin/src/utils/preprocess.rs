@@ -0,0 +1,232 @@
+use std::fmt::Write;
+
+use clap::{ArgAction, ArgMatches, Command};
+use hemtt_common::{position::Position, reporting::Processed, workspace::WorkspacePath};
+use hemtt_preprocessor::{Expansion, Processor};
+
+use crate::{report::Report, utils::standalone, Error};
+
+#[must_use]
+pub fn cli() -> Command {
+    Command::new("preprocess")
+        .about("Print the preprocessed output of a file")
+        .long_about(
+            "Preprocess a file the same way HEMTT does while building, and print the output, or trace how its macros were expanded",
+        )
+        .arg(
+            clap::Arg::new("file")
+                .help("File to preprocess")
+                .required(true),
+        )
+        .arg(
+            clap::Arg::new("line-markers")
+                .long("line-markers")
+                .help("Add #line markers where the output comes from a different place in the source")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("trace")
+                .long("trace")
+                .value_name("MACRO|LINE")
+                .help("Show each expansion of a macro, or of the macros used on a line of the file")
+                .conflicts_with("line-markers"),
+        )
+        .arg(standalone::include_arg())
+}
+
+/// Execute the preprocess command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+///
+/// # Panics
+/// If the args are not present from clap
+pub fn execute(matches: &ArgMatches) -> Result<Report, Error> {
+    let file = std::path::PathBuf::from(matches.get_one::<String>("file").expect("required"));
+    let path = standalone::workspace(&file, &standalone::includes(matches))?;
+    let mut report = Report::new();
+    let (processed, expansions) = match Processor::run_with_trace(&path) {
+        Ok(processed) => processed,
+        Err(hemtt_preprocessor::Error::Code(e)) => {
+            report.error(e);
+            return Ok(report);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    report.add_warnings(processed.warnings().to_vec());
+    if let Some(target) = matches.get_one::<String>("trace") {
+        let trace = trace(&expansions, &path, target);
+        if trace.is_empty() {
+            warn!("no expansions of `{target}` were found");
+        }
+        print!("{trace}");
+    } else if matches.get_flag("line-markers") {
+        print!("{}", with_line_markers(&processed));
+    } else {
+        print!("{}", processed.as_str());
+    }
+    Ok(report)
+}
+
+/// Prints the expansions of a macro, or of the macros used on a line of the root file,
+/// with every expansion that was nested inside them
+fn trace(expansions: &[Expansion], root: &WorkspacePath, target: &str) -> String {
+    let line = target.parse::<usize>().ok();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < expansions.len() {
+        let expansion = &expansions[i];
+        let selected = line.map_or_else(
+            || expansion.name() == target,
+            |line| {
+                expansion.depth() == 0
+                    && expansion.callsite().path() == root
+                    && expansion.callsite().start().line() == line
+            },
+        );
+        if !selected {
+            i += 1;
+            continue;
+        }
+        let depth = expansion.depth();
+        out.push_str(&step(expansion, 0));
+        i += 1;
+        while i < expansions.len() && expansions[i].depth() > depth {
+            out.push_str(&step(&expansions[i], expansions[i].depth() - depth));
+            i += 1;
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// A single expansion, indented by how deeply it is nested
+fn step(expansion: &Expansion, depth: usize) -> String {
+    let indent = "    ".repeat(depth);
+    let output = expansion
+        .output()
+        .trim()
+        .replace('\n', &format!("\n{indent}    "));
+    format!(
+        "{indent}{}  at {}\n{indent}  defined at {}\n{indent}  = {output}\n",
+        expansion.call(),
+        location(expansion.callsite()),
+        location(expansion.definition()),
+    )
+}
+
+fn location(position: &Position) -> String {
+    format!(
+        "{}:{}:{}",
+        position.path(),
+        position.start().line(),
+        position.start().column() + 1
+    )
+}
+
+/// Adds a `#line` marker before each line of output that does not follow on from the line before it
+fn with_line_markers(processed: &Processed) -> String {
+    let mut out = String::with_capacity(processed.as_str().len());
+    let mut expected: Option<(String, usize)> = None;
+    let mut offset = 0;
+    for line in processed.as_str().split_inclusive('\n') {
+        let start = offset + (line.len() - line.trim_start().len());
+        offset += line.len();
+        if line.trim().is_empty() {
+            if let Some((_, next)) = expected.as_mut() {
+                *next += 1;
+            }
+            out.push_str(line);
+            continue;
+        }
+        if let Some(mapping) = processed.mapping(start) {
+            let original = mapping.original();
+            let file = original.path().to_string();
+            let line_number = original.start().line();
+            if expected.as_ref() != Some(&(file.clone(), line_number)) {
+                let _ = writeln!(out, "#line {line_number} \"{file}\"");
+            }
+            expected = Some((file, line_number + 1));
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use hemtt_preprocessor::Processor;
+
+    use crate::utils::standalone;
+
+    use super::{trace, with_line_markers};
+
+    fn source() -> hemtt_common::workspace::WorkspacePath {
+        standalone::workspace(Path::new("tests/preprocess/source.hpp"), &[]).unwrap()
+    }
+
+    #[test]
+    fn line_markers() {
+        let processed = Processor::run(&source()).unwrap();
+        // both lines of the multi-line macro come from the line it is used on
+        assert_eq!(
+            with_line_markers(&processed).replace('\r', ""),
+            "#line 2 \"/source.hpp\"\nclass abe_main {\n    name = \"abe_main_heal\";\n\n    \n\
+             #line 5 \"/source.hpp\"\n    enabled = 1; \n\
+             #line 5 \"/source.hpp\"\n    delay = 5;\n};\n"
+        );
+    }
+
+    #[test]
+    fn trace_macro() {
+        let path = source();
+        let (_, expansions) = Processor::run_with_trace(&path).unwrap();
+        assert_eq!(
+            trace(&expansions, &path, "ADDON").replace('\r', ""),
+            r"ADDON  at /source.hpp:2:7
+  defined at /macros.hpp:4:9
+  = abe_main
+    DOUBLES(abe,main)  at /macros.hpp:4:15
+      defined at /macros.hpp:3:9
+      = abe_main
+        PREFIX  at /macros.hpp:4:23
+          defined at /macros.hpp:1:9
+          = abe
+        COMPONENT  at /macros.hpp:4:30
+          defined at /macros.hpp:2:9
+          = main
+
+ADDON  at /macros.hpp:5:27
+  defined at /macros.hpp:4:9
+  = abe_main
+    DOUBLES(abe,main)  at /macros.hpp:4:15
+      defined at /macros.hpp:3:9
+      = abe_main
+        PREFIX  at /macros.hpp:4:23
+          defined at /macros.hpp:1:9
+          = abe
+        COMPONENT  at /macros.hpp:4:30
+          defined at /macros.hpp:2:9
+          = main
+
+"
+        );
+        assert_eq!(trace(&expansions, &path, "MISSING"), "");
+    }
+
+    #[test]
+    fn trace_line() {
+        let path = source();
+        let (_, expansions) = Processor::run_with_trace(&path).unwrap();
+        let trace_3 = trace(&expansions, &path, "3").replace('\r', "");
+        assert!(trace_3.starts_with(
+            "QGVAR(heal)  at /source.hpp:3:12\n  defined at /macros.hpp:7:9\n  = \"abe_main_heal\"\n    QUOTE(abe_main_heal)  at /macros.hpp:7:20\n"
+        ));
+        // only expansions nested in the one on the line are included
+        assert_eq!(trace_3.matches("\n\n").count(), 1);
+        assert!(trace_3.contains("\n                    DOUBLES(abe,main)  at /macros.hpp:4:15\n"));
+        assert_eq!(trace(&expansions, &path, "4"), "");
+    }
+}
@@ -0,0 +1,178 @@
+use clap::{ArgAction, ArgMatches, Command};
+use hemtt_common::{reporting::Processed, workspace::WorkspacePath};
+use hemtt_preprocessor::Processor;
+use hemtt_sqf::{
+    parser::{database::Database, ParserError},
+    Statements,
+};
+use serde_json::Value;
+
+use crate::{report::Report, utils::standalone, Error};
+
+#[must_use]
+pub fn cli() -> Command {
+    Command::new("ast")
+        .about("Print the parsed syntax tree of an SQF file")
+        .arg(clap::Arg::new("file").help("File to parse").required(true))
+        .arg(
+            clap::Arg::new("json")
+                .long("json")
+                .help("Print the tree as JSON, with the original location of each span")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(standalone::include_arg())
+}
+
+/// Execute the ast command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+///
+/// # Panics
+/// If the args are not present from clap
+pub fn execute(matches: &ArgMatches) -> Result<Report, Error> {
+    let file = std::path::PathBuf::from(matches.get_one::<String>("file").expect("required"));
+    let path = standalone::workspace(&file, &standalone::includes(matches))?;
+    let (_, database) = standalone::project(&std::env::current_dir()?)?;
+    let (report, parsed) = parse(&path, &database)?;
+    if let Some((processed, statements)) = parsed {
+        if matches.get_flag("json") {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json(&statements, &processed)?)?
+            );
+        } else {
+            println!("{statements:#?}");
+        }
+    }
+    Ok(report)
+}
+
+/// Preprocess and parse a file, keeping the statements that could be parsed
+/// when there are parsing errors
+fn parse(
+    path: &WorkspacePath,
+    database: &Database,
+) -> Result<(Report, Option<(Processed, Statements)>), Error> {
+    let mut report = Report::new();
+    let processed = match Processor::run(path) {
+        Ok(processed) => processed,
+        Err(hemtt_preprocessor::Error::Code(e)) => {
+            report.error(e);
+            return Ok((report, None));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let statements = match hemtt_sqf::parser::run(database, &processed) {
+        Ok(statements) => Some(statements),
+        Err(ParserError::ParsingError(errors, partial)) => {
+            report.add_errors(errors);
+            partial
+        }
+        Err(ParserError::LexingError(errors)) => {
+            report.add_errors(errors);
+            None
+        }
+    };
+    Ok((report, statements.map(|statements| (processed, statements))))
+}
+
+/// The statements as JSON, with the original location of every span
+fn json(statements: &Statements, processed: &Processed) -> Result<Value, Error> {
+    let mut value = serde_json::to_value(statements)?;
+    locate(&mut value, processed);
+    Ok(value)
+}
+
+/// Adds the original file, line, and column to every span, and the macro it came from
+fn locate(value: &mut Value, processed: &Processed) {
+    match value {
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                locate(value, processed);
+            }
+            let Some(Value::Object(span)) = map.get_mut("span") else {
+                return;
+            };
+            let Some(mapping) = span
+                .get("start")
+                .and_then(Value::as_u64)
+                .and_then(|start| usize::try_from(start).ok())
+                .and_then(|start| processed.mapping(start))
+            else {
+                return;
+            };
+            let original = mapping.original();
+            span.insert(String::from("file"), Value::from(original.path().as_str()));
+            span.insert(String::from("line"), Value::from(original.start().line()));
+            span.insert(
+                String::from("column"),
+                Value::from(original.start().column()),
+            );
+            if mapping.was_macro() {
+                span.insert(
+                    String::from("macro"),
+                    Value::from(mapping.token().to_source()),
+                );
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                locate(value, processed);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use hemtt_sqf::parser::database::Database;
+
+    use crate::{report::WithIncludes, utils::standalone};
+
+    use super::{json, parse};
+
+    const ROOT: &str = "tests/ast";
+
+    #[test]
+    fn json_snapshot() {
+        let path = standalone::workspace(&Path::new(ROOT).join("source.sqf"), &[]).unwrap();
+        let (report, parsed) = parse(&path, &Database::default()).unwrap();
+        assert!(report.errors(WithIncludes::Yes).is_empty());
+        let (processed, statements) = parsed.unwrap();
+        let value = json(&statements, &processed).unwrap();
+
+        // the array element that came from the macro points at the macro in the file
+        let element = &value[0]["expression"]["elements"][1];
+        assert_eq!(element["kind"], "BinaryCommand");
+        assert_eq!(element["command"], "*");
+        assert_eq!(element["span"]["file"], "/source.sqf");
+        assert_eq!(element["span"]["line"], 2);
+        assert_eq!(element["span"]["macro"], "DOUBLE");
+        // nested code blocks do not repeat their source
+        let code = &value[2]["expression"]["left"];
+        assert_eq!(code["kind"], "Code");
+        assert!(code.get("source").is_none());
+        assert_eq!(code["statements"][0]["name"], "GVAR_total");
+
+        let output = serde_json::to_string_pretty(&value).unwrap() + "\n";
+        let snapshot = PathBuf::from(ROOT).join("source.json");
+        let expected = std::fs::read_to_string(&snapshot).unwrap();
+        if expected.is_empty() {
+            std::fs::write(&snapshot, &output).unwrap();
+        }
+        assert_eq!(output.replace('\r', ""), expected.replace('\r', ""));
+    }
+
+    #[test]
+    fn partial() {
+        let path = standalone::workspace(&Path::new(ROOT).join("broken.sqf"), &[]).unwrap();
+        let (report, parsed) = parse(&path, &Database::default()).unwrap();
+        assert!(!report.errors(WithIncludes::Yes).is_empty());
+        let (_, statements) = parsed.unwrap();
+        assert!(!statements.content().is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+use std::path::{Path, PathBuf};
+
+use clap::{ArgMatches, Command};
+use hemtt_preprocessor::Processor;
+use hemtt_sqf::{analyze::analyze, parser::ParserError};
+
+use crate::{report::Report, utils::standalone, Error};
+
+#[must_use]
+pub fn cli() -> Command {
+    Command::new("check")
+        .about("Check SQF files outside of a project")
+        .long_about(
+            "Preprocess, parse, and analyze SQF files, such as mission scripts, without a HEMTT project",
+        )
+        .arg(standalone::files_arg())
+        .arg(standalone::include_arg())
+}
+
+/// Execute the check command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(matches: &ArgMatches) -> Result<Report, Error> {
+    let (files, includes) = standalone::args(matches);
+    check(&std::env::current_dir()?, &files, &includes)
+}
+
+/// Check SQF files, with the project in `folder` if there is one
+fn check(folder: &Path, files: &[PathBuf], includes: &[PathBuf]) -> Result<Report, Error> {
+    let (project, database) = standalone::project(folder)?;
+    let mut report = Report::new();
+    for file in files {
+        let path = standalone::workspace(file, includes)?;
+        let processed = match Processor::run(&path) {
+            Ok(processed) => processed,
+            Err(hemtt_preprocessor::Error::Code(e)) => {
+                report.error(e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        for warning in processed.warnings() {
+            report.warn(warning.clone());
+        }
+        let statements = match hemtt_sqf::parser::run(&database, &processed) {
+            Ok(statements) => statements,
+            Err(ParserError::ParsingError(errors, partial)) => {
+                report.add_errors(errors);
+                let Some(statements) = partial else {
+                    continue;
+                };
+                statements
+            }
+            Err(ParserError::LexingError(errors)) => {
+                report.add_errors(errors);
+                continue;
+            }
+        };
+        let (warnings, errors) =
+            analyze(&statements, project.as_ref(), &processed, None, &database);
+        report.add_warnings(warnings);
+        report.add_errors(errors);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::report::WithIncludes;
+
+    use super::check;
+
+    #[test]
+    fn project_commands() {
+        let files = [PathBuf::from("tests/standalone/mission/init.sqf")];
+        let includes = [PathBuf::from("tests/standalone/include")];
+        // the custom command is only known inside the project
+        let report = check(Path::new("tests/standalone"), &files, &includes).unwrap();
+        assert!(report.errors(WithIncludes::Yes).is_empty());
+        let report = check(Path::new("tests/standalone/mission"), &files, &includes).unwrap();
+        assert!(!report.errors(WithIncludes::Yes).is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+
+use clap::{ArgAction, ArgMatches, Command};
+use hemtt_common::project::{sqf::FormatOptions, ProjectConfig};
+
+use crate::Error;
+
+#[must_use]
+pub fn cli() -> Command {
+    Command::new("fmt")
+        .about("Format SQF files")
+        .long_about(
+            "Format SQF files, using the style options from `[sqf.format]` in `.hemtt/project.toml` if it exists",
+        )
+        .arg(
+            clap::Arg::new("paths")
+                .help("Files or folders to format, defaults to the current folder")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            clap::Arg::new("check")
+                .long("check")
+                .help("Only check if the files are formatted, without changing them")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+/// Execute the fmt command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+/// [`Error::Unformatted`] if `--check` is used and files are not formatted
+/// [`Error::FormatSkipped`] if `--check` is used and files could not be formatted
+pub fn execute(matches: &ArgMatches) -> Result<(), Error> {
+    let check = matches.get_flag("check");
+    let project = Path::new(".hemtt").join("project.toml");
+    let options = if project.exists() {
+        ProjectConfig::from_file(&project)?.sqf().format().clone()
+    } else {
+        FormatOptions::default()
+    };
+    let paths = matches.get_many::<String>("paths").map_or_else(
+        || vec![PathBuf::from(".")],
+        |paths| paths.map(PathBuf::from).collect(),
+    );
+    format(&paths, &options, check)
+}
+
+fn format(paths: &[PathBuf], options: &FormatOptions, check: bool) -> Result<(), Error> {
+    let mut unformatted = 0;
+    let mut skipped = 0;
+    for path in paths {
+        for file in sqf_files(path)? {
+            let source = std::fs::read_to_string(&file)?;
+            let formatted = match hemtt_sqf::formatter::format(&source, options) {
+                Ok(formatted) => formatted,
+                Err(e) => {
+                    warn!("Skipped {}: {e}", file.display());
+                    skipped += 1;
+                    continue;
+                }
+            };
+            if formatted == source {
+                continue;
+            }
+            if check {
+                error!("Not formatted: {}", file.display());
+                unformatted += 1;
+            } else {
+                std::fs::write(&file, formatted)?;
+                info!("Formatted {}", file.display());
+            }
+        }
+    }
+    if skipped > 0 {
+        warn!("{skipped} file(s) could not be formatted");
+    }
+    if unformatted > 0 {
+        return Err(Error::Unformatted(unformatted));
+    }
+    // a file that could not be formatted is not known to be formatted
+    if check && skipped > 0 {
+        return Err(Error::FormatSkipped(skipped));
+    }
+    Ok(())
+}
+
+/// The SQF files at a path, skipping hidden folders such as `.hemttout`
+fn sqf_files(path: &Path) -> Result<Vec<PathBuf>, Error> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+    {
+        let entry = entry?;
+        if entry.file_type().is_file()
+            && entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("sqf"))
+        {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use hemtt_common::project::sqf::FormatOptions;
+
+    use crate::Error;
+
+    use super::format;
+
+    #[test]
+    fn check_skipped() {
+        let paths = [PathBuf::from("tests/fmt")];
+        assert!(matches!(
+            format(&paths, &FormatOptions::default(), true),
+            Err(Error::FormatSkipped(1))
+        ));
+        let paths = [PathBuf::from("tests/fmt/formatted.sqf")];
+        assert!(format(&paths, &FormatOptions::default(), true).is_ok());
+    }
+}
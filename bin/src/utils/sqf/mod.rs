@@ -0,0 +1,30 @@
+mod ast;
+mod check;
+mod fmt;
+
+use clap::{ArgMatches, Command};
+
+use crate::{report::Report, Error};
+
+#[must_use]
+pub fn cli() -> Command {
+    Command::new("sqf")
+        .about("Commands for SQF files")
+        .arg_required_else_help(true)
+        .subcommand(ast::cli())
+        .subcommand(check::cli())
+        .subcommand(fmt::cli())
+}
+
+/// Execute the sqf command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(matches: &ArgMatches) -> Result<Report, Error> {
+    match matches.subcommand() {
+        Some(("ast", matches)) => ast::execute(matches),
+        Some(("check", matches)) => check::execute(matches),
+        Some(("fmt", matches)) => fmt::execute(matches).map(|()| Report::new()),
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,125 @@
+//! Workspaces for checking files outside of a project
+
+use std::path::{Path, PathBuf};
+
+use clap::{Arg, ArgAction, ArgMatches};
+use hemtt_common::{
+    project::ProjectConfig,
+    workspace::{LayerType, Workspace, WorkspacePath},
+};
+use hemtt_sqf::parser::database::Database;
+
+use crate::{modules::sqf::database, Error};
+
+#[must_use]
+/// The `-I` argument, for folders to search for includes
+pub fn include_arg() -> Arg {
+    Arg::new("include")
+        .short('I')
+        .long("include")
+        .help("Folders to search for includes, such as a project's `include` folder")
+        .action(ArgAction::Append)
+}
+
+#[must_use]
+/// The `files` argument
+pub fn files_arg() -> Arg {
+    Arg::new("files")
+        .help("Files to check")
+        .required(true)
+        .action(ArgAction::Append)
+}
+
+/// The files and include folders passed to a command
+///
+/// # Panics
+/// If the args are not present from clap
+pub fn args(matches: &ArgMatches) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let files = matches
+        .get_many::<String>("files")
+        .expect("required")
+        .map(PathBuf::from)
+        .collect();
+    (files, includes(matches))
+}
+
+#[must_use]
+/// The include folders passed to a command
+pub fn includes(matches: &ArgMatches) -> Vec<PathBuf> {
+    matches
+        .get_many::<String>("include")
+        .map(|includes| includes.map(PathBuf::from).collect())
+        .unwrap_or_default()
+}
+
+/// Creates a throwaway workspace containing the folder of a file, and the include folders
+///
+/// # Errors
+/// [`Error::Io`] if the file or an include folder does not exist
+/// [`Error::Workspace`] if the workspace could not be created
+///
+/// # Panics
+/// If the canonical path of the file has no file name
+pub fn workspace(file: &Path, includes: &[PathBuf]) -> Result<WorkspacePath, Error> {
+    let file = file.canonicalize()?;
+    let folder = file
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+    let mut builder = Workspace::builder().physical(&folder, LayerType::Source);
+    for include in includes {
+        builder = builder.physical(&include.canonicalize()?, LayerType::Include);
+    }
+    Ok(builder.finish(None)?.join(
+        file.file_name()
+            .expect("canonical file paths have a name")
+            .to_string_lossy()
+            .as_ref(),
+    )?)
+}
+
+/// The project in a folder, if it has a `.hemtt/project.toml`, and the command database
+/// with any commands it declares
+///
+/// # Errors
+/// [`Error::Project`] if the project.toml is not valid
+/// [`Error::Io`] if the commands file can not be read
+pub fn project(folder: &Path) -> Result<(Option<ProjectConfig>, Database), Error> {
+    let path = folder.join(".hemtt").join("project.toml");
+    if !path.exists() {
+        return Ok((None, Database::default()));
+    }
+    let config = ProjectConfig::from_file(&path)?;
+    let database = database(&config, folder)?;
+    Ok((Some(config), database))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{project, workspace};
+
+    #[test]
+    fn project_database() {
+        let (config, database) = project(Path::new("tests/standalone")).unwrap();
+        assert_eq!(config.unwrap().prefix(), "sa");
+        assert!(database.has_binary_command("interceptSend"));
+        let (config, database) = project(Path::new("tests/standalone/mission")).unwrap();
+        assert!(config.is_none());
+        assert!(!database.has_binary_command("interceptSend"));
+    }
+
+    #[test]
+    fn include_folders() {
+        let path = workspace(
+            Path::new("tests/standalone/mission/init.sqf"),
+            &[PathBuf::from("tests/standalone/include")],
+        )
+        .unwrap();
+        assert_eq!(path.as_str(), "/init.sqf");
+        // found the same way as `#include "macros.hpp"` in the file
+        assert!(path.locate("macros.hpp").unwrap().is_some());
+        assert!(path.locate("description.ext").unwrap().is_some());
+        assert!(path.locate("project.toml").unwrap().is_none());
+    }
+}
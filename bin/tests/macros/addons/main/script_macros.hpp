@@ -0,0 +1,6 @@
+#define EXPANDED 1
+#define CHECKED
+#define NEVER_USED 2
+#define INACTIVE 3
+#define DEBUG_MODE 4
+#define SHARED(var) (var + 1)
@@ -0,0 +1,4 @@
+#include "..\main\script_macros.hpp"
+
+#define SHARED(var) (var + 1)
+#define DEBUG_MODE 5
@@ -0,0 +1,14 @@
+#define PREFIX abe
+#define COMPONENT main
+
+#define GVAR(var) PREFIX##_##COMPONENT##_##var
+
+#ifdef DEBUG_MODE_FULL
+    #define TRACE_1(message,arg1) diag_log format ["%1: %2", message, arg1]
+#else
+    #define TRACE_1(message,arg1) /* disabled */
+#endif
+
+#define HEAL_ALL(units) { \
+    _x setDamage 0; \
+} forEach units
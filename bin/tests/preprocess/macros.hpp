@@ -0,0 +1,10 @@
+#define PREFIX abe
+#define COMPONENT main
+#define DOUBLES(a,b) a##_##b
+#define ADDON DOUBLES(PREFIX,COMPONENT)
+#define GVAR(var) DOUBLES(ADDON,var)
+#define QUOTE(var) #var
+#define QGVAR(var) QUOTE(GVAR(var))
+#define SETTINGS \
+    enabled = 1; \
+    delay = 5
@@ -0,0 +1,6 @@
+#include "macros.hpp"
+class ADDON {
+    name = QGVAR(heal);
+
+    SETTINGS;
+};
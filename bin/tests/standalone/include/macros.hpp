@@ -0,0 +1 @@
+#define SEND(a,b) a interceptSend b
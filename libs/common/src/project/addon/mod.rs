@@ -34,6 +34,10 @@ pub struct AddonConfig {
     #[serde(default)]
     /// Files to exclude from the pbo
     files: FilesConfig,
+
+    #[serde(default)]
+    /// Preprocessor options for the addon
+    preprocessor: super::preprocessor::Options,
 }
 
 impl AddonConfig {
@@ -90,6 +94,12 @@ impl AddonConfig {
         config.exclude.append(&mut self.exclude.clone());
         config
     }
+
+    #[must_use]
+    /// Preprocessor options for the addon
+    pub const fn preprocessor(&self) -> &super::preprocessor::Options {
+        &self.preprocessor
+    }
 }
 
 impl FromStr for AddonConfig {
@@ -2,7 +2,7 @@
 
 use std::{borrow::Cow, collections::HashMap};
 
-use crate::arma::dlc::DLC;
+use crate::{arma::dlc::DLC, reporting::ReportFormat};
 use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
@@ -19,6 +19,9 @@ pub struct Features {
 
     #[serde(default)]
     release: ReleaseOptions,
+
+    #[serde(default)]
+    report_format: Vec<ReportFormat>,
 }
 
 impl Features {
@@ -51,6 +54,12 @@ impl Features {
     pub const fn release(&self) -> &ReleaseOptions {
         &self.release
     }
+
+    #[must_use]
+    /// Formats the report is written in, in addition to the terminal
+    pub fn report_format(&self) -> &[ReportFormat] {
+        &self.report_format
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
@@ -178,6 +187,10 @@ pub struct ReleaseOptions {
     /// Create an archive of the release
     /// Default: true
     archive: Option<bool>,
+    #[serde(default)]
+    /// Should SQF files be minified?
+    /// Default: false
+    minify: Option<bool>,
 }
 
 impl ReleaseOptions {
@@ -206,4 +219,14 @@ impl ReleaseOptions {
             true
         }
     }
+
+    #[must_use]
+    /// Should SQF files be minified?
+    pub const fn minify(&self) -> bool {
+        if let Some(minify) = self.minify {
+            minify
+        } else {
+            false
+        }
+    }
 }
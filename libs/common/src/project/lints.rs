@@ -0,0 +1,247 @@
+use std::{collections::HashMap, fmt::Display, hash::BuildHasher};
+
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+/// Lint configuration
+pub struct Options {
+    #[serde(default)]
+    /// SQF lint options
+    sqf: SqfOptions,
+
+    #[serde(default)]
+    /// Levels that only apply to addons matching a glob pattern
+    addons: HashMap<String, HashMap<String, Level>>,
+
+    #[serde(default, flatten)]
+    /// Levels for codes across the whole project, such as `SAA7 = "allow"`
+    codes: HashMap<String, Level>,
+}
+
+impl Options {
+    #[must_use]
+    /// SQF lint options
+    pub const fn sqf(&self) -> &SqfOptions {
+        &self.sqf
+    }
+
+    #[must_use]
+    /// Levels that only apply to addons matching a glob pattern
+    pub const fn addons(&self) -> &HashMap<String, HashMap<String, Level>> {
+        &self.addons
+    }
+
+    #[must_use]
+    /// The level set for a code across the whole project
+    pub fn level(&self, code: &str) -> Option<Level> {
+        level(&self.codes, code)
+    }
+
+    /// Check that no level is set for an error
+    ///
+    /// # Errors
+    /// [`Error::ConfigInvalid`] if a level is set for an error, such as `PE12`
+    pub fn validate(&self) -> Result<(), Error> {
+        for code in self
+            .codes
+            .keys()
+            .chain(self.addons.values().flat_map(HashMap::keys))
+        {
+            if is_error(code) {
+                return Err(Error::ConfigInvalid(format!(
+                    "lints can not change the level of `{code}`, errors are always reported as errors"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[must_use]
+/// Is a code an error, such as `PE12` or `SAE1`
+///
+/// The level of an error can not be changed, and it can not be suppressed
+pub fn is_error(code: &str) -> bool {
+    let letters = code.trim_end_matches(|c: char| c.is_ascii_digit());
+    letters.len() < code.len() && letters.to_ascii_uppercase().ends_with('E')
+}
+
+#[must_use]
+/// The level set for a code in a table of levels
+///
+/// Codes are not case sensitive
+pub fn level<S: BuildHasher>(codes: &HashMap<String, Level, S>, code: &str) -> Option<Level> {
+    codes
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(code))
+        .map(|(_, level)| *level)
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+/// How a code is reported
+pub enum Level {
+    /// The code is not reported
+    Allow,
+    /// The code is reported as a warning
+    Warn,
+    /// The code is reported as an error, and fails the build
+    Deny,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Allow => write!(f, "allow"),
+            Self::Warn => write!(f, "warn"),
+            Self::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+impl Serialize for Level {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Level {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(LevelVisitor)
+    }
+}
+
+/// Reads a level from `"allow"`, `"warn"`, or `"deny"`,
+/// or from `false` and `true` as `allow` and `warn`
+struct LevelVisitor;
+
+impl Visitor<'_> for LevelVisitor {
+    type Value = Level;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("`allow`, `warn`, `deny`, or a boolean")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(if v { Level::Warn } else { Level::Allow })
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match v.to_lowercase().as_str() {
+            "allow" => Ok(Level::Allow),
+            "warn" => Ok(Level::Warn),
+            "deny" => Ok(Level::Deny),
+            _ => Err(E::invalid_value(serde::de::Unexpected::Str(v), &self)),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+/// SQF lint options
+pub struct SqfOptions {
+    #[serde(default)]
+    /// Deprecated command options
+    deprecated: DeprecatedOptions,
+}
+
+impl SqfOptions {
+    #[must_use]
+    /// Deprecated command options
+    pub const fn deprecated(&self) -> &DeprecatedOptions {
+        &self.deprecated
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+/// Deprecated command options
+pub struct DeprecatedOptions {
+    #[serde(default)]
+    /// Should deprecated commands be reported?
+    /// Default: true
+    enabled: Option<bool>,
+
+    #[serde(default)]
+    /// Commands and functions that should not be reported
+    ignore: Vec<String>,
+}
+
+impl DeprecatedOptions {
+    #[must_use]
+    /// Should deprecated commands be reported?
+    pub const fn enabled(&self) -> bool {
+        if let Some(enabled) = self.enabled {
+            enabled
+        } else {
+            true
+        }
+    }
+
+    #[must_use]
+    /// Is the command or function ignored by the project
+    pub fn is_ignored(&self, command: &str) -> bool {
+        self.ignore.iter().any(|i| i.eq_ignore_ascii_case(command))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Level, Options};
+
+    #[test]
+    fn levels() {
+        let options: Options = toml::from_str(
+            r#"
+SAA7 = "allow"
+saw10 = "deny"
+PW3 = true
+CW1 = false
+
+[addons."compat_*"]
+SAW1 = "allow"
+
+[sqf.deprecated]
+ignore = ["BIS_fnc_MP"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(options.level("SAA7"), Some(Level::Allow));
+        assert_eq!(options.level("SAW10"), Some(Level::Deny));
+        assert_eq!(options.level("PW3"), Some(Level::Warn));
+        assert_eq!(options.level("CW1"), Some(Level::Allow));
+        assert_eq!(options.level("SAW1"), None);
+        assert_eq!(
+            super::level(&options.addons()["compat_*"], "SAW1"),
+            Some(Level::Allow)
+        );
+        assert!(options.sqf().deprecated().is_ignored("bis_fnc_mp"));
+        assert!(toml::from_str::<Options>("SAA7 = \"error\"").is_err());
+        assert!(options.validate().is_ok());
+    }
+
+    #[test]
+    fn errors() {
+        assert!(super::is_error("PE12"));
+        assert!(super::is_error("sae1"));
+        assert!(super::is_error("BCLE3"));
+        assert!(!super::is_error("PW1"));
+        assert!(!super::is_error("SAA7"));
+        assert!(!super::is_error("E"));
+        let options: Options = toml::from_str("PE12 = \"allow\"").unwrap();
+        assert!(options.validate().is_err());
+        let options: Options = toml::from_str("[addons.\"*\"]\nCE3 = \"warn\"").unwrap();
+        assert!(options.validate().is_err());
+    }
+}
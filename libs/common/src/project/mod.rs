@@ -8,7 +8,10 @@ use tracing::warn;
 mod addon;
 mod files;
 pub mod hemtt;
+pub mod lints;
+pub mod preprocessor;
 mod signing;
+pub mod sqf;
 mod version;
 
 pub use {crate::error::Error, addon::*};
@@ -43,6 +46,15 @@ pub struct ProjectConfig {
 
     #[serde(default)]
     signing: signing::Options,
+
+    #[serde(default)]
+    lints: lints::Options,
+
+    #[serde(default)]
+    sqf: sqf::Options,
+
+    #[serde(default)]
+    preprocessor: preprocessor::Options,
 }
 
 impl ProjectConfig {
@@ -94,6 +106,24 @@ impl ProjectConfig {
         &self.signing
     }
 
+    #[must_use]
+    /// Lint options
+    pub const fn lints(&self) -> &lints::Options {
+        &self.lints
+    }
+
+    #[must_use]
+    /// SQF options
+    pub const fn sqf(&self) -> &sqf::Options {
+        &self.sqf
+    }
+
+    #[must_use]
+    /// Preprocessor options
+    pub const fn preprocessor(&self) -> &preprocessor::Options {
+        &self.preprocessor
+    }
+
     #[must_use]
     /// The folder name to use for the release
     /// Default: `@{prefix}`
@@ -118,6 +148,10 @@ impl ProjectConfig {
         if config.prefix.is_empty() {
             return Err(Error::ConfigInvalid("prefix cannot be empty".to_string()));
         }
+        for command in config.sqf.commands() {
+            command.validate()?;
+        }
+        config.lints.validate()?;
 
         CONFIG_DEPRECATION.call_once(|| {
             if file.contains("[asc]") {
@@ -144,7 +178,7 @@ impl FromStr for ProjectConfig {
 mod tests {
     use std::collections::HashMap;
 
-    use super::{files, hemtt, signing, version};
+    use super::{files, hemtt, lints, preprocessor, signing, sqf, version};
 
     impl super::ProjectConfig {
         #[must_use]
@@ -158,6 +192,9 @@ mod tests {
                 files: files::Options::default(),
                 hemtt: hemtt::Features::default(),
                 signing: signing::Options::default(),
+                lints: lints::Options::default(),
+                sqf: sqf::Options::default(),
+                preprocessor: preprocessor::Options::default(),
             }
         }
     }
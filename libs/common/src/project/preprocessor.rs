@@ -0,0 +1,188 @@
+use std::{collections::HashMap, hash::BuildHasher};
+
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+/// Preprocessor configuration
+pub struct Options {
+    #[serde(default)]
+    /// Macros defined before every file is preprocessed
+    defines: HashMap<String, Define>,
+
+    #[serde(default)]
+    /// Options that only apply when building in a mode, such as `dev` or `release`
+    modes: HashMap<String, ModeOptions>,
+}
+
+impl Options {
+    #[must_use]
+    /// Macros defined before every file is preprocessed
+    pub const fn defines(&self) -> &HashMap<String, Define> {
+        &self.defines
+    }
+
+    #[must_use]
+    /// Options that only apply when building in a mode, such as `dev` or `release`
+    pub const fn modes(&self) -> &HashMap<String, ModeOptions> {
+        &self.modes
+    }
+
+    /// Apply the defines for a mode on top of `defines`
+    ///
+    /// The defines of the mode override the defines that apply to every mode
+    pub fn apply<S: BuildHasher>(&self, mode: &str, defines: &mut HashMap<String, String, S>) {
+        apply(&self.defines, defines);
+        if let Some(options) = self.modes.get(mode) {
+            apply(&options.defines, defines);
+        }
+    }
+}
+
+fn apply<S: BuildHasher>(
+    layer: &HashMap<String, Define>,
+    defines: &mut HashMap<String, String, S>,
+) {
+    for (name, define) in layer {
+        match define {
+            Define::Value(value) => {
+                defines.insert(name.clone(), value.clone());
+            }
+            Define::Unset => {
+                defines.remove(name);
+            }
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+/// Preprocessor options for a build mode
+pub struct ModeOptions {
+    #[serde(default)]
+    /// Macros defined before every file is preprocessed
+    defines: HashMap<String, Define>,
+}
+
+impl ModeOptions {
+    #[must_use]
+    /// Macros defined before every file is preprocessed
+    pub const fn defines(&self) -> &HashMap<String, Define> {
+        &self.defines
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+/// A macro defined from the configuration
+pub enum Define {
+    /// The macro is defined with a value, which may be empty
+    Value(String),
+    /// The macro is removed, if an earlier layer defined it
+    Unset,
+}
+
+impl Serialize for Define {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Value(value) if value.is_empty() => serializer.serialize_bool(true),
+            Self::Value(value) => serializer.serialize_str(value),
+            Self::Unset => serializer.serialize_bool(false),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Define {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DefineVisitor)
+    }
+}
+
+/// Reads a define from a string or number used as its value,
+/// or from `true` for a define without a value and `false` to remove it
+struct DefineVisitor;
+
+impl Visitor<'_> for DefineVisitor {
+    type Value = Define;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a string, a number, or a boolean")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(if v {
+            Define::Value(String::new())
+        } else {
+            Define::Unset
+        })
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Define::Value(v.to_string()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Define::Value(v.to_string()))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Define::Value(v.to_string()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Define::Value(v.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::Options;
+
+    #[test]
+    fn defines() {
+        let options: Options = toml::from_str(
+            r#"
+[defines]
+DEBUG_MODE_FULL = true
+MAX_UNITS = 40
+GREETING = "\"hello\""
+
+[modes.release.defines]
+DEBUG_MODE_FULL = false
+HEMTT_RELEASE = true
+"#,
+        )
+        .unwrap();
+        let mut dev = HashMap::new();
+        options.apply("dev", &mut dev);
+        assert_eq!(dev.len(), 3);
+        assert_eq!(dev["DEBUG_MODE_FULL"], "");
+        assert_eq!(dev["MAX_UNITS"], "40");
+        assert_eq!(dev["GREETING"], "\"hello\"");
+        let mut release = HashMap::new();
+        options.apply("release", &mut release);
+        assert!(!release.contains_key("DEBUG_MODE_FULL"));
+        assert_eq!(release["HEMTT_RELEASE"], "");
+        assert!(toml::from_str::<Options>("[defines]\nA = [1]").is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Config properties that contain SQF by default
+const CONFIG_PROPERTIES: &[&str] = &[
+    "init",
+    "statement",
+    "condition",
+    "onLoad",
+    "action",
+    "expression",
+];
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+/// SQF options
+pub struct Options {
+    #[serde(default)]
+    /// Additional commands, such as those added by Intercept or other extensions
+    commands: Vec<Command>,
+
+    #[serde(default)]
+    /// A JSON file containing an array of additional commands, relative to the project root
+    commands_file: Option<String>,
+
+    #[serde(default)]
+    /// Style options for `hemtt utils sqf fmt`
+    format: FormatOptions,
+
+    #[serde(default)]
+    /// Config properties whose strings contain SQF that should be checked
+    /// Default: `init`, `statement`, `condition`, `onLoad`, `action`, `expression`
+    config_properties: Option<Vec<String>>,
+}
+
+impl Options {
+    #[must_use]
+    /// Additional commands declared in project.toml
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    #[must_use]
+    /// A JSON file containing an array of additional commands
+    pub const fn commands_file(&self) -> Option<&String> {
+        self.commands_file.as_ref()
+    }
+
+    #[must_use]
+    /// Style options for the formatter
+    pub const fn format(&self) -> &FormatOptions {
+        &self.format
+    }
+
+    #[must_use]
+    /// Config properties whose strings contain SQF that should be checked
+    pub fn config_properties(&self) -> Vec<String> {
+        self.config_properties.clone().unwrap_or_else(|| {
+            CONFIG_PROPERTIES
+                .iter()
+                .map(|property| (*property).to_string())
+                .collect()
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+/// Style options for the SQF formatter
+pub struct FormatOptions {
+    #[serde(default)]
+    /// The number of spaces per indentation level
+    /// Default: 4
+    indent: Option<usize>,
+
+    #[serde(default)]
+    /// Where the opening brace of a multi-line code block is placed
+    braces: BraceStyle,
+
+    #[serde(default)]
+    /// Should binary commands such as `+` and `==` be surrounded by spaces?
+    /// Default: true
+    binary_spaces: Option<bool>,
+
+    #[serde(default)]
+    /// Should the last statement of a multi-line code block end with a semicolon?
+    /// Default: true
+    trailing_semicolons: Option<bool>,
+}
+
+impl FormatOptions {
+    #[must_use]
+    /// The number of spaces per indentation level
+    pub fn indent(&self) -> usize {
+        self.indent.unwrap_or(4)
+    }
+
+    #[must_use]
+    /// Where the opening brace of a multi-line code block is placed
+    pub const fn braces(&self) -> BraceStyle {
+        self.braces
+    }
+
+    #[must_use]
+    /// Should binary commands such as `+` and `==` be surrounded by spaces?
+    pub fn binary_spaces(&self) -> bool {
+        self.binary_spaces.unwrap_or(true)
+    }
+
+    #[must_use]
+    /// Should the last statement of a multi-line code block end with a semicolon?
+    pub fn trailing_semicolons(&self) -> bool {
+        self.trailing_semicolons.unwrap_or(true)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+/// Where the opening brace of a multi-line code block is placed
+pub enum BraceStyle {
+    #[default]
+    /// `if (...) then {`
+    SameLine,
+    /// `if (...) then` followed by `{` on its own line
+    NextLine,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+/// A command that is not part of the base game
+pub struct Command {
+    /// The name of the command
+    name: String,
+    /// The arity of the command
+    syntax: Syntax,
+
+    #[serde(default)]
+    /// The version of Arma 3 the command was added in, such as `2.14`
+    since: Option<String>,
+
+    #[serde(default)]
+    /// The type of the left argument, for binary commands
+    left: Option<ArgumentType>,
+
+    #[serde(default)]
+    /// The type of the right argument, for unary and binary commands
+    right: Option<ArgumentType>,
+}
+
+impl Command {
+    #[must_use]
+    pub const fn new(name: String, syntax: Syntax) -> Self {
+        Self {
+            name,
+            syntax,
+            since: None,
+            left: None,
+            right: None,
+        }
+    }
+
+    /// Check that the argument types match the arity of the command
+    ///
+    /// # Errors
+    /// [`Error::ConfigInvalid`] if a nular or unary command has a left argument type,
+    /// or a nular command has a right argument type
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.left.is_some() && self.syntax != Syntax::Binary {
+            return Err(Error::ConfigInvalid(format!(
+                "sqf command `{}` is {}, only binary commands have a `left` argument",
+                self.name, self.syntax
+            )));
+        }
+        if self.right.is_some() && self.syntax == Syntax::Nular {
+            return Err(Error::ConfigInvalid(format!(
+                "sqf command `{}` is nular, it has no `right` argument",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    /// The name of the command
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    /// The arity of the command
+    pub const fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+
+    #[must_use]
+    /// The version of Arma 3 the command was added in
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_deref()
+    }
+
+    #[must_use]
+    /// The type of the left argument, for binary commands
+    pub const fn left(&self) -> Option<ArgumentType> {
+        self.left
+    }
+
+    #[must_use]
+    /// The type of the right argument, for unary and binary commands
+    pub const fn right(&self) -> Option<ArgumentType> {
+        self.right
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// The arity of a command
+pub enum Syntax {
+    /// `command`
+    Nular,
+    /// `command right`
+    Unary,
+    /// `left command right`
+    Binary,
+}
+
+impl Display for Syntax {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Nular => write!(f, "nular"),
+            Self::Unary => write!(f, "unary"),
+            Self::Binary => write!(f, "binary"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+/// The type of an argument of a command
+pub enum ArgumentType {
+    Anything,
+    Array,
+    Boolean,
+    Code,
+    Config,
+    Control,
+    Display,
+    Group,
+    HashMap,
+    Location,
+    Namespace,
+    Number,
+    Object,
+    Side,
+    String,
+    StructuredText,
+}
+
+impl Display for ArgumentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
@@ -0,0 +1,364 @@
+//! Machine readable reports, for CI platforms and other tools
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+use codespan_reporting::{
+    diagnostic::{LabelStyle, Severity},
+    files::Files,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha1::{Digest, Sha1};
+
+use crate::reporting::WorkspaceFiles;
+
+use super::{Diagnostic, Label};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// A format a report can be written in
+pub enum ReportFormat {
+    /// HEMTT's own JSON format, with every label, note and help
+    Json,
+    /// SARIF 2.1.0, for GitHub code scanning
+    Sarif,
+    /// GitLab Code Quality
+    GitLab,
+}
+
+impl ReportFormat {
+    #[must_use]
+    /// The name of the file the report is written to in `.hemttout`
+    pub const fn file_name(self) -> &'static str {
+        match self {
+            Self::Json => "report.json",
+            Self::Sarif => "report.sarif",
+            Self::GitLab => "gl-code-quality-report.json",
+        }
+    }
+
+    #[must_use]
+    /// Render diagnostics in this format
+    ///
+    /// `version` is the version of HEMTT, reported as the version of the tool
+    pub fn render(
+        self,
+        diagnostics: &[Diagnostic],
+        files: &WorkspaceFiles,
+        version: &str,
+    ) -> Value {
+        let fingerprints = fingerprints(diagnostics, files);
+        match self {
+            Self::Json => json(diagnostics, files, &fingerprints),
+            Self::Sarif => sarif(diagnostics, files, &fingerprints, version),
+            Self::GitLab => gitlab(diagnostics, files, &fingerprints),
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            "gitlab" => Ok(Self::GitLab),
+            _ => Err(format!(
+                "unknown report format `{s}`, expected `json`, `sarif` or `gitlab`"
+            )),
+        }
+    }
+}
+
+impl Display for ReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Sarif => write!(f, "sarif"),
+            Self::GitLab => write!(f, "gitlab"),
+        }
+    }
+}
+
+/// Where a label is in its file, with 1-based lines and columns
+struct Location {
+    path: String,
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl Location {
+    fn of(label: &Label, files: &WorkspaceFiles) -> Option<Self> {
+        let start_line_index = files.line_index(&label.file, label.span.start).ok()?;
+        let end_line_index = files.line_index(&label.file, label.span.end).ok()?;
+        Some(Self {
+            path: label.file.source_path(),
+            start_line: files.line_number(&label.file, start_line_index).ok()?,
+            start_column: files
+                .column_number(&label.file, start_line_index, label.span.start)
+                .ok()?,
+            end_line: files.line_number(&label.file, end_line_index).ok()?,
+            end_column: files
+                .column_number(&label.file, end_line_index, label.span.end)
+                .ok()?,
+        })
+    }
+}
+
+impl Diagnostic {
+    #[must_use]
+    /// A fingerprint that does not change when lines are added above the diagnostic
+    ///
+    /// It is made from the code, the message, the file, and the text of the line of the primary label
+    pub fn fingerprint(&self, files: &WorkspaceFiles) -> String {
+        let mut hasher = Sha1::new();
+        hasher.update(self.code.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.message.as_bytes());
+        if let Some(label) = self.primary_label() {
+            hasher.update([0]);
+            hasher.update(label.file.as_str().as_bytes());
+            if let (Ok(source), Ok(line)) = (
+                files.source(&label.file),
+                files.line_index(&label.file, label.span.start),
+            ) {
+                if let Ok(range) = files.line_range(&label.file, line) {
+                    hasher.update([0]);
+                    hasher.update(source.get(range).unwrap_or_default().trim().as_bytes());
+                }
+            }
+        }
+        hasher
+            .finalize()
+            .iter()
+            .fold(String::new(), |mut out, byte| {
+                let _ = write!(out, "{byte:02x}");
+                out
+            })
+    }
+}
+
+/// A unique fingerprint for each diagnostic
+///
+/// Identical diagnostics are told apart by the order they were reported in
+fn fingerprints(diagnostics: &[Diagnostic], files: &WorkspaceFiles) -> Vec<String> {
+    let mut seen = HashMap::new();
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut fingerprint = diagnostic.fingerprint(files);
+            let occurrence = seen.entry(fingerprint.clone()).or_insert(0);
+            *occurrence += 1;
+            if *occurrence > 1 {
+                let _ = write!(fingerprint, ":{occurrence}");
+            }
+            fingerprint
+        })
+        .collect()
+}
+
+const fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+fn json(diagnostics: &[Diagnostic], files: &WorkspaceFiles, fingerprints: &[String]) -> Value {
+    Value::Array(
+        diagnostics
+            .iter()
+            .zip(fingerprints)
+            .map(|(diagnostic, fingerprint)| {
+                json!({
+                    "code": diagnostic.code,
+                    "severity": severity_name(diagnostic.severity),
+                    "message": diagnostic.message,
+                    "labels": diagnostic.labels.iter().filter_map(|label| {
+                        let location = Location::of(label, files)?;
+                        Some(json!({
+                            "primary": label.style == LabelStyle::Primary,
+                            "message": label.message,
+                            "path": location.path,
+                            "start_line": location.start_line,
+                            "start_column": location.start_column,
+                            "end_line": location.end_line,
+                            "end_column": location.end_column,
+                        }))
+                    }).collect::<Vec<_>>(),
+                    "notes": diagnostic.notes,
+                    "help": diagnostic.help,
+                    "suggestions": diagnostic.suggestions,
+                    "fingerprint": fingerprint,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn sarif_location(label: &Label, files: &WorkspaceFiles) -> Option<Value> {
+    let location = Location::of(label, files)?;
+    let mut value = json!({
+        "physicalLocation": {
+            "artifactLocation": {
+                "uri": location.path,
+                "uriBaseId": "%SRCROOT%",
+            },
+            "region": {
+                "startLine": location.start_line,
+                "startColumn": location.start_column,
+                "endLine": location.end_line,
+                "endColumn": location.end_column,
+            },
+        },
+    });
+    if let Some(message) = &label.message {
+        value["message"] = json!({ "text": message });
+    }
+    Some(value)
+}
+
+fn sarif(
+    diagnostics: &[Diagnostic],
+    files: &WorkspaceFiles,
+    fingerprints: &[String],
+    version: &str,
+) -> Value {
+    let mut rules = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.code.as_str())
+        .collect::<Vec<_>>();
+    rules.sort_unstable();
+    rules.dedup();
+    let results = diagnostics
+        .iter()
+        .zip(fingerprints)
+        .map(|(diagnostic, fingerprint)| {
+            let level = match diagnostic.severity {
+                Severity::Bug | Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Note | Severity::Help => "note",
+            };
+            let mut text = diagnostic.message.clone();
+            for note in &diagnostic.notes {
+                let _ = write!(text, "\nnote: {note}");
+            }
+            for help in &diagnostic.help {
+                let _ = write!(text, "\nhelp: {help}");
+            }
+            json!({
+                "ruleId": diagnostic.code,
+                "ruleIndex": rules.binary_search(&diagnostic.code.as_str()).unwrap_or_default(),
+                "level": level,
+                "message": { "text": text },
+                "locations": diagnostic.primary_label()
+                    .and_then(|label| sarif_location(label, files))
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+                "relatedLocations": diagnostic
+                    .labels
+                    .iter()
+                    .filter(|label| label.style == LabelStyle::Secondary)
+                    .filter_map(|label| sarif_location(label, files))
+                    .collect::<Vec<_>>(),
+                "partialFingerprints": { "hemtt/v1": fingerprint },
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "HEMTT",
+                    "informationUri": "https://github.com/BrettMayson/HEMTT",
+                    "version": version,
+                    "rules": rules.iter().map(|rule| json!({ "id": rule })).collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn gitlab(diagnostics: &[Diagnostic], files: &WorkspaceFiles, fingerprints: &[String]) -> Value {
+    Value::Array(
+        diagnostics
+            .iter()
+            .zip(fingerprints)
+            // every issue needs a location in the project
+            .filter_map(|(diagnostic, fingerprint)| {
+                let location = Location::of(diagnostic.primary_label()?, files)?;
+                let severity = match diagnostic.severity {
+                    Severity::Bug => "blocker",
+                    Severity::Error => "critical",
+                    Severity::Warning => "minor",
+                    Severity::Note | Severity::Help => "info",
+                };
+                Some(json!({
+                    "description": format!("[{}] {}", diagnostic.code, diagnostic.message),
+                    "check_name": diagnostic.code,
+                    "fingerprint": fingerprint,
+                    "severity": severity,
+                    "location": {
+                        "path": location.path,
+                        "lines": {
+                            "begin": location.start_line,
+                            "end": location.end_line,
+                        },
+                    },
+                }))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        reporting::{Diagnostic, Label, WorkspaceFiles},
+        workspace::Workspace,
+    };
+
+    use super::{fingerprints, ReportFormat};
+
+    #[test]
+    fn stable_fingerprints() {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let path = workspace.join("test.sqf").unwrap();
+        let diagnostic = |start: usize| {
+            Diagnostic::new("SAW1", "undefined variable")
+                .with_label(Label::primary(path.clone(), start..start + 2))
+        };
+        path.create_file()
+            .unwrap()
+            .write_all(b"private _a = 1;\nhint _b;\n")
+            .unwrap();
+        let files = WorkspaceFiles::new();
+        let first = fingerprints(&[diagnostic(21)], &files);
+        let twice = fingerprints(&[diagnostic(21), diagnostic(21)], &files);
+        assert_eq!(twice[0], first[0]);
+        assert_eq!(twice[1], format!("{}:2", first[0]));
+        let gitlab = ReportFormat::GitLab.render(&[diagnostic(21)], &files, "1.0.0");
+        assert_eq!(gitlab[0]["location"]["lines"]["begin"], 2);
+        assert_eq!(gitlab[0]["severity"], "critical");
+        // adding a line above does not change the fingerprint
+        path.create_file()
+            .unwrap()
+            .write_all(b"// added\nprivate _a = 1;\nhint _b;\n")
+            .unwrap();
+        let files = WorkspaceFiles::new();
+        assert_eq!(fingerprints(&[diagnostic(30)], &files), first);
+    }
+}
@@ -9,11 +9,13 @@ use codespan_reporting::{
 use crate::workspace::WorkspacePath;
 
 use self::annotation::Annotation;
+pub use self::format::ReportFormat;
 pub use self::label::Label;
 
 use super::{Code, WorkspaceFiles};
 
 mod annotation;
+mod format;
 mod label;
 
 #[derive(Debug, Clone)]
@@ -41,6 +43,14 @@ impl Diagnostic {
         }
     }
 
+    #[must_use]
+    /// The primary label, that points at where the diagnostic was reported
+    pub fn primary_label(&self) -> Option<&Label> {
+        self.labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+    }
+
     pub fn new_for_processed(
         code: &impl Code,
         span: std::ops::Range<usize>,
@@ -63,6 +73,7 @@ impl Diagnostic {
             )
             .with_message(code.label_message()),
         );
+        diag = diag.with_expansions(span.start, processed);
         if let Some(note) = code.note() {
             diag.notes.push(note);
         }
@@ -75,6 +86,47 @@ impl Diagnostic {
         Some(diag)
     }
 
+    #[must_use]
+    /// Adds secondary labels for the macro expansions that produced an offset of the processed output
+    ///
+    /// The chain is walked from the expansion in the file inwards, labelling where each
+    /// nested macro was expanded and where each macro was defined
+    pub fn with_expansions(
+        mut self,
+        offset: usize,
+        processed: &crate::reporting::Processed,
+    ) -> Self {
+        let mut expansions = processed
+            .mappings(offset)
+            .into_iter()
+            .filter(|mapping| mapping.was_macro())
+            .filter_map(|mapping| Some((mapping, processed.declaration(mapping.original())?)))
+            .collect::<Vec<_>>();
+        // the outermost expansion is the last mapping, and is usually the primary label
+        expansions.reverse();
+        for (mapping, definition) in expansions {
+            let name = mapping.token().to_source();
+            for (position, message) in [
+                (mapping.original(), format!("in this expansion of `{name}`")),
+                (definition, format!("`{name}` defined here")),
+            ] {
+                if self.labels.iter().any(|label| {
+                    label.file() == position.path() && label.span.start == position.start().0
+                }) {
+                    continue;
+                }
+                self.labels.push(
+                    Label::secondary(
+                        position.path().clone(),
+                        position.start().0..position.end().0,
+                    )
+                    .with_message(message),
+                );
+            }
+        }
+        self
+    }
+
     pub fn simple(code: &impl Code) -> Self {
         let mut diag = Self::new(code.ident(), code.message()).set_severity(code.severity());
         if let Some(note) = code.note() {
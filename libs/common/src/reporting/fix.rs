@@ -0,0 +1,79 @@
+use std::ops::Range;
+
+use crate::workspace::WorkspacePath;
+
+use super::Processed;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An edit to an original source file that resolves a code
+///
+/// Only created where the edit is safe to apply without review
+pub struct Fix {
+    file: WorkspacePath,
+    /// The bytes of the original file that are replaced
+    span: Range<usize>,
+    replacement: String,
+}
+
+impl Fix {
+    #[must_use]
+    /// Replace a span of an original source file
+    pub const fn new(file: WorkspacePath, span: Range<usize>, replacement: String) -> Self {
+        Self {
+            file,
+            span,
+            replacement,
+        }
+    }
+
+    #[must_use]
+    /// Replace a span of the processed output, by mapping it back to its original source
+    ///
+    /// Returns `None` when the span does not map to a single run of text in one file,
+    /// such as when any part of it came from a macro
+    pub fn for_processed(
+        span: Range<usize>,
+        replacement: impl Into<String>,
+        processed: &Processed,
+    ) -> Option<Self> {
+        if span.is_empty() {
+            return None;
+        }
+        let start = processed.mappings(span.start);
+        let end = processed.mappings(span.end - 1);
+        if start.iter().chain(end.iter()).any(|map| map.was_macro()) {
+            return None;
+        }
+        let (start, end) = (start.last()?.original(), end.last()?.original());
+        if start.path() != end.path() || start.path().is_include() {
+            return None;
+        }
+        let original = start.start().0..end.end().0;
+        if original.is_empty() {
+            return None;
+        }
+        Some(Self::new(
+            start.path().clone(),
+            original,
+            replacement.into(),
+        ))
+    }
+
+    #[must_use]
+    /// The file the edit applies to
+    pub const fn file(&self) -> &WorkspacePath {
+        &self.file
+    }
+
+    #[must_use]
+    /// The bytes of the original file that are replaced
+    pub const fn span(&self) -> &Range<usize> {
+        &self.span
+    }
+
+    #[must_use]
+    /// The text that replaces the span
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
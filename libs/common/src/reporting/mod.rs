@@ -4,17 +4,21 @@ use std::fmt::Debug;
 
 pub mod diagnostic;
 mod files;
+mod fix;
 mod output;
 mod processed;
+mod suppression;
 mod symbol;
 mod token;
 mod whitespace;
 
 pub use codespan_reporting::diagnostic::Severity;
-pub use diagnostic::{Diagnostic, Label};
+pub use diagnostic::{Diagnostic, Label, ReportFormat};
 pub use files::{WorkspaceFile, WorkspaceFiles};
+pub use fix::Fix;
 pub use output::Output;
-pub use processed::{Mapping, Processed};
+pub use processed::{MacroDefinition, Mapping, Processed};
+pub use suppression::{is_ignored, Suppression};
 pub use symbol::Symbol;
 pub use token::Token;
 pub use whitespace::Whitespace;
@@ -49,6 +53,10 @@ pub trait Code: Send + Sync {
     fn suggestion(&self) -> Option<String> {
         None
     }
+    /// An edit that resolves the code, if one can be applied safely
+    fn fix(&self) -> Option<Fix> {
+        None
+    }
 
     /// A diagnostic for the LSP / terminal
     fn diagnostic(&self) -> Option<Diagnostic> {
@@ -1,6 +1,6 @@
-#[cfg(feature = "lsp")]
-use std::collections::HashMap;
-use std::{rc::Rc, sync::Arc};
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+use codespan_reporting::diagnostic::LabelStyle;
 
 use crate::{
     position::{LineCol, Position},
@@ -8,7 +8,7 @@ use crate::{
     workspace::{Error, WorkspacePath},
 };
 
-use super::Code;
+use super::{suppression, Code, Suppression};
 
 #[derive(Debug, Default)]
 /// A processed file
@@ -22,16 +22,20 @@ pub struct Processed {
     /// string offset(start, stop), source, source position
     mappings: Vec<Mapping>,
 
-    #[cfg(feature = "lsp")]
     /// Map of token usage to definition
     /// (token, definition)
     declarations: HashMap<Position, Position>,
 
-    #[cfg(feature = "lsp")]
     /// Map of token definition to usage
     /// (definition, usages)
     usage: HashMap<Position, Vec<Position>>,
 
+    /// Macros defined by `#define`, in the order they were defined
+    definitions: Vec<MacroDefinition>,
+
+    /// Files included by `#include`, directly or by an included file
+    includes: Vec<WorkspacePath>,
+
     line: usize,
     col: usize,
     total: usize,
@@ -39,6 +43,9 @@ pub struct Processed {
     /// Warnings
     warnings: Vec<Arc<dyn Code>>,
 
+    /// Codes suppressed by `#pragma hemtt suppress`
+    suppressions: Vec<Suppression>,
+
     /// The preprocessor was able to check the file, but it should not be rapified
     no_rapify: bool,
 }
@@ -172,27 +179,89 @@ impl Processed {
     ///
     /// # Errors
     /// [`Error::Workspace`] if a workspace path could not be read
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         output: Vec<Output>,
-        #[cfg(feature = "lsp")] usage: HashMap<Position, Vec<Position>>,
-        #[cfg(feature = "lsp")] declarations: HashMap<Position, Position>,
+        usage: HashMap<Position, Vec<Position>>,
+        declarations: HashMap<Position, Position>,
+        definitions: Vec<MacroDefinition>,
+        includes: Vec<WorkspacePath>,
         warnings: Vec<Arc<dyn Code>>,
+        suppressions: Vec<Suppression>,
         no_rapify: bool,
     ) -> Result<Self, Error> {
         let mut processed = Self {
-            #[cfg(feature = "lsp")]
             declarations,
-            #[cfg(feature = "lsp")]
             usage,
-            warnings,
+            definitions,
+            includes,
+            suppressions,
             no_rapify,
             ..Default::default()
         };
         let mut string_stack = Vec::new();
         append_output(&mut processed, &mut string_stack, output)?;
+        processed.warnings = processed.unsuppressed(warnings);
         Ok(processed)
     }
 
+    #[must_use]
+    /// Create a processed file from a section of this one, such as SQF inside a config string
+    ///
+    /// `offsets` holds the byte offset in this file of each character of `output`,
+    /// so the new file maps back to the same original sources
+    pub fn extract(&self, output: String, offsets: &[usize]) -> Self {
+        let mut positions = Vec::with_capacity(offsets.len() + 2);
+        let mut line_offsets = Vec::new();
+        let (mut line, mut col) = (0, 0);
+        for (i, c) in output.char_indices() {
+            positions.push(LineCol(i, (line, col)));
+            if c == '\n' {
+                line_offsets.push(i);
+                line += 1;
+                col = 0;
+            } else {
+                col += c.len_utf8();
+            }
+        }
+        let total = output.len();
+        positions.push(LineCol(total, (line, col)));
+        // mappings that reach the end also cover the offset after it, like the end of a file
+        positions.push(LineCol(total + 1, (line, col + 1)));
+        let mappings = self
+            .mappings
+            .iter()
+            .filter_map(|mapping| {
+                let start =
+                    offsets.partition_point(|&offset| offset < mapping.processed_start().offset());
+                let mut end =
+                    offsets.partition_point(|&offset| offset < mapping.processed_end().offset());
+                if start < end && end == offsets.len() {
+                    end += 1;
+                }
+                (start < end).then(|| Mapping {
+                    source: mapping.source,
+                    processed: (positions[start], positions[end]),
+                    original: mapping.original.clone(),
+                    token: mapping.token.clone(),
+                    was_macro: mapping.was_macro,
+                })
+            })
+            .collect();
+        Self {
+            sources: self.sources.clone(),
+            output,
+            line_offsets,
+            mappings,
+            line,
+            col,
+            total,
+            declarations: self.declarations.clone(),
+            suppressions: self.suppressions.clone(),
+            ..Default::default()
+        }
+    }
+
     #[must_use]
     /// Get the output suitable for further processing
     /// Ignores certain tokens
@@ -253,6 +322,74 @@ impl Processed {
         &self.warnings
     }
 
+    #[must_use]
+    /// Where each macro was used, by the position of its definition
+    pub const fn usage(&self) -> &HashMap<Position, Vec<Position>> {
+        &self.usage
+    }
+
+    #[must_use]
+    /// The definition of the macro used at a position
+    pub fn declaration(&self, usage: &Position) -> Option<&Position> {
+        self.declarations.get(usage)
+    }
+
+    #[must_use]
+    /// Macros defined by `#define`, in the order they were defined
+    pub fn definitions(&self) -> &[MacroDefinition] {
+        &self.definitions
+    }
+
+    #[must_use]
+    /// Files included by `#include`, directly or by an included file,
+    /// even those that only define macros or check them with `#ifdef`
+    pub fn includes(&self) -> &[WorkspacePath] {
+        &self.includes
+    }
+
+    #[must_use]
+    /// Codes suppressed by `#pragma hemtt suppress`
+    pub fn suppressions(&self) -> &[Suppression] {
+        &self.suppressions
+    }
+
+    #[must_use]
+    /// Is a code suppressed where it is reported, by `#pragma hemtt suppress`
+    /// or by a `// hemtt-ignore` comment
+    pub fn is_suppressed(&self, code: &dyn Code) -> bool {
+        let Some(diagnostic) = code.diagnostic() else {
+            return false;
+        };
+        let Some(label) = diagnostic
+            .labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+        else {
+            return false;
+        };
+        let Some((_, content)) = self.sources.iter().find(|(path, _)| path == label.file()) else {
+            return false;
+        };
+        let line = content
+            .bytes()
+            .take(label.span.start)
+            .filter(|b| *b == b'\n')
+            .count();
+        self.suppressions
+            .iter()
+            .any(|s| s.covers(code.ident(), label.file(), line))
+            || suppression::is_ignored(content, line, code.ident())
+    }
+
+    #[must_use]
+    /// Removes codes that are suppressed where they are reported
+    pub fn unsuppressed(&self, codes: Vec<Arc<dyn Code>>) -> Vec<Arc<dyn Code>> {
+        codes
+            .into_iter()
+            .filter(|code| !self.is_suppressed(code.as_ref()))
+            .collect()
+    }
+
     #[must_use]
     /// Returns whether the file should not be rapified
     pub const fn no_rapify(&self) -> bool {
@@ -260,6 +397,59 @@ impl Processed {
     }
 }
 
+#[derive(Debug, Clone)]
+/// A macro defined by `#define`
+pub struct MacroDefinition {
+    name: String,
+    position: Position,
+    body: String,
+    replaces: Option<Position>,
+}
+
+impl MacroDefinition {
+    #[must_use]
+    /// Create a new macro definition
+    ///
+    /// `body` is the arguments and body of the macro, as they would be written after its name
+    pub const fn new(
+        name: String,
+        position: Position,
+        body: String,
+        replaces: Option<Position>,
+    ) -> Self {
+        Self {
+            name,
+            position,
+            body,
+            replaces,
+        }
+    }
+
+    #[must_use]
+    /// The name of the macro
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    /// The position of the name in the `#define`
+    pub const fn position(&self) -> &Position {
+        &self.position
+    }
+
+    #[must_use]
+    /// The arguments and body of the macro, with whitespace collapsed
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    #[must_use]
+    /// The definition this one replaced, when the macro was already defined
+    pub const fn replaces(&self) -> Option<&Position> {
+        self.replaces.as_ref()
+    }
+}
+
 #[derive(Debug)]
 /// A mapping from the stringified output to the original source
 pub struct Mapping {
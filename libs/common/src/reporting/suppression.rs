@@ -0,0 +1,82 @@
+use std::ops::Range;
+
+use crate::workspace::WorkspacePath;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A code suppressed by `#pragma hemtt suppress`
+pub struct Suppression {
+    code: String,
+    /// The file the suppression applies to, or `None` for every file
+    file: Option<WorkspacePath>,
+    /// Lines the suppression applies to, starting from 0
+    lines: Range<usize>,
+}
+
+impl Suppression {
+    #[must_use]
+    /// Suppress a code on a single line of a file
+    pub const fn line(code: String, file: WorkspacePath, line: usize) -> Self {
+        Self {
+            code,
+            file: Some(file),
+            lines: line..line + 1,
+        }
+    }
+
+    #[must_use]
+    /// Suppress a code from a line to the end of a file
+    pub const fn file(code: String, file: WorkspacePath, line: usize) -> Self {
+        Self {
+            code,
+            file: Some(file),
+            lines: line..usize::MAX,
+        }
+    }
+
+    #[must_use]
+    /// Suppress a code in every file that is part of the output
+    pub const fn config(code: String) -> Self {
+        Self {
+            code,
+            file: None,
+            lines: 0..usize::MAX,
+        }
+    }
+
+    #[must_use]
+    /// Does the suppression apply to a code at a line of a file
+    pub fn covers(&self, code: &str, file: &WorkspacePath, line: usize) -> bool {
+        self.code.eq_ignore_ascii_case(code)
+            && (self.file.is_none() || self.file.as_ref() == Some(file))
+            && self.lines.contains(&line)
+    }
+}
+
+#[must_use]
+/// Is a code listed by a `// hemtt-ignore` comment on a line
+///
+/// The comment applies to the line it is on, or to the next line when it is on a line of its own
+pub fn is_ignored(content: &str, line: usize, code: &str) -> bool {
+    let mut lines = content.lines().skip(line.saturating_sub(1));
+    if line > 0 {
+        if let Some(previous) = lines.next() {
+            if previous.trim_start().starts_with("//") && ignores(previous, code) {
+                return true;
+            }
+        }
+    }
+    lines.next().is_some_and(|current| ignores(current, code))
+}
+
+/// Does the comment on a line list a code after `hemtt-ignore`
+fn ignores(line: &str, code: &str) -> bool {
+    let Some(start) = line.find("//") else {
+        return false;
+    };
+    let Some(codes) = line[start + 2..].trim_start().strip_prefix("hemtt-ignore") else {
+        return false;
+    };
+    codes
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .any(|c| c.eq_ignore_ascii_case(code))
+}
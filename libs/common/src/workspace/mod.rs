@@ -22,6 +22,8 @@ use crate::{
 pub struct Workspace {
     pub(crate) vfs: VfsPath,
     pub(crate) layers: Vec<(VfsPath, LayerType)>,
+    /// Include layers that are inside the source folder, with their folder relative to it
+    pub(crate) include_folders: Vec<(VfsPath, String)>,
     pub(crate) project: Option<ProjectConfig>,
     pub(crate) pointers: HashMap<String, VfsPath>,
     pub(crate) addons: Vec<VfsPath>,
@@ -48,11 +50,13 @@ impl Workspace {
     pub fn create(
         vfs: VfsPath,
         layers: Vec<(VfsPath, LayerType)>,
+        include_folders: Vec<(VfsPath, String)>,
         project: Option<ProjectConfig>,
     ) -> Result<WorkspacePath, Error> {
         let mut workspace = Self {
             vfs,
             layers,
+            include_folders,
             project,
             pointers: HashMap::new(),
             addons: Vec::new(),
@@ -109,16 +113,17 @@ pub enum LayerType {
 /// A workspace builder
 pub struct WorkspaceBuilder {
     layers: Vec<(VfsPath, LayerType)>,
+    physical: Vec<(PathBuf, VfsPath, LayerType)>,
 }
 
 impl WorkspaceBuilder {
     #[must_use]
     /// Add a physical layer to the virtual filesystem
     pub fn physical(mut self, path: &PathBuf, layer_type: LayerType) -> Self {
-        self.layers.push((
-            AltrootFS::new(PhysicalFS::new(path).into()).into(),
-            layer_type,
-        ));
+        let layer: VfsPath = AltrootFS::new(PhysicalFS::new(path).into()).into();
+        self.physical
+            .push((path.clone(), layer.clone(), layer_type.clone()));
+        self.layers.push((layer, layer_type));
         self
     }
 
@@ -136,9 +141,25 @@ impl WorkspaceBuilder {
     pub fn finish(self, project: Option<ProjectConfig>) -> Result<WorkspacePath, Error> {
         let mut layers = self.layers.clone();
         layers.reverse();
+        let include_folders = self
+            .physical
+            .iter()
+            .find(|(_, _, t)| *t == LayerType::Source)
+            .map(|(source, _, _)| {
+                self.physical
+                    .iter()
+                    .filter(|(_, _, t)| *t == LayerType::Include)
+                    .filter_map(|(path, layer, _)| {
+                        let folder = path.strip_prefix(source).ok()?;
+                        Some((layer.clone(), folder.to_string_lossy().replace('\\', "/")))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
         Workspace::create(
             OverlayFS::new(&layers.into_iter().map(|(l, _)| l).collect::<Vec<_>>()).into(),
             self.layers,
+            include_folders,
             project,
         )
     }
@@ -40,6 +40,37 @@ impl WorkspacePath {
             })
     }
 
+    #[must_use]
+    /// The path of the file relative to the source folder, without a leading `/`
+    ///
+    /// Files from an include folder inside the source folder, such as a project's
+    /// `include` folder, are given the path they have in the source folder
+    pub fn source_path(&self) -> String {
+        let path = self.path.as_str().trim_start_matches('/');
+        let exists = |layer: &VfsPath| {
+            layer
+                .join(self.path.as_str())
+                .and_then(|p| p.exists())
+                .unwrap_or(false)
+        };
+        if self
+            .workspace
+            .layers
+            .iter()
+            .any(|(l, t)| *t == LayerType::Source && exists(l))
+        {
+            return path.to_string();
+        }
+        self.workspace
+            .include_folders
+            .iter()
+            .find(|(l, _)| exists(l))
+            .map_or_else(
+                || path.to_string(),
+                |(_, folder)| format!("{folder}/{path}"),
+            )
+    }
+
     /// join a path to the workspace path
     ///
     /// # Errors
@@ -1,6 +1,6 @@
 use codespan_reporting::diagnostic::Severity;
 use hemtt_common::{
-    reporting::{Diagnostic, Label, WorkspaceFiles},
+    reporting::{Diagnostic, Label, ReportFormat, WorkspaceFiles},
     workspace::LayerType,
 };
 
@@ -36,3 +36,64 @@ fn python() {
     }
     assert_eq!(diagnostic, expected);
 }
+
+#[test]
+fn sarif() {
+    let folder = std::path::PathBuf::from("tests/report");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .physical(&folder.join("include"), LayerType::Include)
+        .finish(None)
+        .unwrap();
+    let diagnostics = [
+        Diagnostic::new("PW1".to_string(), "redefining macro".to_string())
+            .set_severity(Severity::Warning)
+            .with_label(Label::primary(
+                workspace.join("abe/macros.hpp").unwrap(),
+                8..13,
+            ))
+            .with_label(Label::secondary(
+                workspace.join("addons/main/fnc_ready.sqf").unwrap(),
+                0..8,
+            ))
+            .with_note("the macro is also defined by CBA".to_string()),
+        Diagnostic::new("PE12".to_string(), "include not found".to_string()).with_label(
+            Label::primary(workspace.join("addons/main/fnc_ready.sqf").unwrap(), 9..27),
+        ),
+    ];
+    let sarif = ReportFormat::Sarif.render(&diagnostics, &WorkspaceFiles::new(), "1.0.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(sarif["version"], "2.1.0");
+    assert_eq!(run["tool"]["driver"]["version"], "1.0.0");
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "PE12");
+    assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "PW1");
+    let warning = &run["results"][0];
+    assert_eq!(warning["ruleId"], "PW1");
+    assert_eq!(warning["ruleIndex"], 1);
+    assert_eq!(warning["level"], "warning");
+    assert_eq!(
+        warning["message"]["text"],
+        "redefining macro\nnote: the macro is also defined by CBA"
+    );
+    // files from the include layer keep their path in the project
+    let location = &warning["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"]["uri"],
+        "include/abe/macros.hpp"
+    );
+    assert_eq!(location["region"]["startLine"], 1);
+    assert_eq!(location["region"]["startColumn"], 9);
+    assert_eq!(
+        warning["relatedLocations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "addons/main/fnc_ready.sqf"
+    );
+    let error = &run["results"][1];
+    assert_eq!(error["level"], "error");
+    assert_eq!(
+        error["locations"][0]["physicalLocation"]["region"]["startLine"],
+        1
+    );
+    let gitlab = ReportFormat::GitLab.render(&diagnostics, &WorkspaceFiles::new(), "1.0.0");
+    assert_eq!(gitlab[0]["location"]["path"], "include/abe/macros.hpp");
+    assert_eq!(gitlab[1]["location"]["path"], "addons/main/fnc_ready.sqf");
+}
@@ -0,0 +1,2 @@
+#define QUOTE(var) #var
+#define GVAR(var) abe_main_##var
@@ -1,4 +1,4 @@
-use hemtt_common::reporting::{Code, Diagnostic, Label, Processed};
+use hemtt_common::reporting::{Code, Diagnostic, Fix, Label, Processed};
 
 use crate::Class;
 
@@ -7,6 +7,7 @@ pub struct ParentCase {
     parent: Class,
 
     diagnostic: Option<Diagnostic>,
+    fix: Option<Fix>,
 }
 
 // TODO: maybe we could have a `did you mean` here without too much trouble?
@@ -38,6 +39,10 @@ impl Code for ParentCase {
         )
     }
 
+    fn fix(&self) -> Option<Fix> {
+        self.fix.clone()
+    }
+
     fn diagnostic(&self) -> Option<Diagnostic> {
         self.diagnostic.clone()
     }
@@ -50,20 +55,22 @@ impl ParentCase {
             parent,
 
             diagnostic: None,
+            fix: None,
         }
         .generate_processed(processed)
     }
 
     fn generate_processed(mut self, processed: &Processed) -> Self {
-        self.diagnostic = Diagnostic::new_for_processed(
-            &self,
-            self.class
-                .parent()
-                .expect("parent existed to create error")
-                .span
-                .clone(),
-            processed,
-        );
+        let span = self
+            .class
+            .parent()
+            .expect("parent existed to create error")
+            .span
+            .clone();
+        self.diagnostic = Diagnostic::new_for_processed(&self, span.clone(), processed);
+        self.fix = self
+            .suggestion()
+            .and_then(|suggestion| Fix::for_processed(span, suggestion, processed));
         if let Some(diag) = &mut self.diagnostic {
             let Some(parent) = self.class.parent() else {
                 panic!("ParentCase::generate_processed called on class without parent");
@@ -44,7 +44,7 @@ pub fn parse(
         },
         |config| {
             Ok(ConfigReport {
-                warnings: config.warnings(project, processed),
+                warnings: processed.unsuppressed(config.warnings(project, processed)),
                 errors: config.errors(project, processed),
                 patches: config.get_patches(),
                 config,
@@ -6,3 +6,17 @@ pub struct Expression {
     pub(crate) value: String,
     pub(crate) span: Range<usize>,
 }
+
+impl Expression {
+    #[must_use]
+    /// Get the value of the expression
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[must_use]
+    /// Get the range of the expression
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
@@ -6,3 +6,17 @@ pub struct Str {
     pub(crate) value: String,
     pub(crate) span: Range<usize>,
 }
+
+impl Str {
+    #[must_use]
+    /// Get the value of the string
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[must_use]
+    /// Get the range of the string
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
@@ -62,6 +62,7 @@ fn check(dir: &str) {
 
 bootstrap!(ce1_invalid_value);
 bootstrap!(ce2_invalid_value_macro);
+bootstrap!(ce2_invalid_value_nested_macro);
 bootstrap!(ce3_duplicate_property_separate);
 bootstrap!(ce3_duplicate_property_shadow_property);
 bootstrap!(ce4_missing_semicolon);
@@ -0,0 +1,6 @@
+#define PATHTO_SYS(x) \some\x
+#define PATHTO(x) PATHTO_SYS(x)
+
+class Test {
+    path = PATHTO(thing);
+};
@@ -0,0 +1,29 @@
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use hemtt_common::workspace::LayerType;
+use hemtt_preprocessor::{Cache, Processor};
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&PathBuf::from("benches"), LayerType::Source)
+        .memory()
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("fnc_small.sqf").unwrap();
+    let defines = HashMap::new();
+    c.bench_function("preprocess - small function", |b| {
+        b.iter(|| {
+            Processor::run(&source).unwrap();
+        });
+    });
+    let cache = Arc::new(Cache::new());
+    c.bench_function("preprocess - small function, cached includes", |b| {
+        b.iter(|| {
+            Processor::run_with_defines(&source, &defines, &cache).unwrap();
+        });
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,83 @@
+//! Tokenized include files shared between preprocessor runs
+
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    sync::{Arc, RwLock},
+};
+
+use hemtt_common::{reporting::Token, workspace::WorkspacePath};
+
+use crate::Error;
+
+#[derive(Debug, Default)]
+/// A cache of tokenized include files, that can be shared between threads
+///
+/// Headers such as `script_component.hpp` are included by every file of an addon,
+/// so they only need to be tokenized once per build
+pub struct Cache {
+    files: RwLock<HashMap<WorkspacePath, Arc<CachedFile>>>,
+}
+
+#[derive(Debug)]
+struct CachedFile {
+    source: String,
+    tokens: Vec<Token>,
+}
+
+impl Cache {
+    #[must_use]
+    /// Create a new empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the tokens of a file, tokenizing it if it is not cached
+    ///
+    /// The file is still read every time, so a file that was changed,
+    /// such as by a hook, is tokenized again
+    pub(crate) fn tokens(&self, path: &WorkspacePath) -> Result<Vec<Rc<Token>>, Error> {
+        let source = path.read_to_string()?;
+        let cached = self.files.read().unwrap().get(path).cloned();
+        if let Some(file) = cached {
+            if file.source == source {
+                return Ok(file.tokens.iter().cloned().map(Rc::new).collect());
+            }
+        }
+        let tokens = crate::parse::str(&source, path)?;
+        self.files.write().unwrap().insert(
+            path.clone(),
+            Arc::new(CachedFile {
+                source,
+                tokens: tokens.iter().map(|token| token.as_ref().clone()).collect(),
+            }),
+        );
+        Ok(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cache;
+
+    #[test]
+    fn changed_file() {
+        let workspace = hemtt_common::workspace::Workspace::builder()
+            .memory()
+            .finish(None)
+            .unwrap();
+        let test = workspace.join("test.hpp").unwrap();
+        test.create_file()
+            .unwrap()
+            .write_all(b"#define A 1")
+            .unwrap();
+        let cache = Cache::new();
+        let first = cache.tokens(&test).unwrap();
+        assert_eq!(cache.tokens(&test).unwrap(), first);
+        test.create_file()
+            .unwrap()
+            .write_all(b"#define A 10")
+            .unwrap();
+        assert_eq!(cache.tokens(&test).unwrap().len(), first.len() + 1);
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use hemtt_common::{
+    project::lints,
     reporting::{Code, Token},
     similar_values,
 };
@@ -8,10 +9,11 @@ use hemtt_common::{
 use crate::{processor::pragma::Suppress, Error};
 
 #[allow(unused)]
-/// An unknown `#pragma hemtt suppress` code
+/// An unknown `#pragma hemtt suppress` code, or an error code
 ///
 /// ```cpp
 /// #pragma hemtt suppress unknown
+/// #pragma hemtt suppress PE12
 /// ```
 pub struct PragmaInvalidSuppress {
     /// The [`Token`] of the code
@@ -28,6 +30,12 @@ impl Code for PragmaInvalidSuppress {
     }
 
     fn message(&self) -> String {
+        if self.is_error() {
+            return format!(
+                "errors can not be suppressed, `{}` is an error",
+                self.token.symbol().to_string(),
+            );
+        }
         format!(
             "unknown #pragma suppress `{}`",
             self.token.symbol().to_string(),
@@ -35,10 +43,16 @@ impl Code for PragmaInvalidSuppress {
     }
 
     fn label_message(&self) -> String {
+        if self.is_error() {
+            return "error code".to_string();
+        }
         "unknown #pragma suppress".to_string()
     }
 
     fn help(&self) -> Option<String> {
+        if self.is_error() {
+            return None;
+        }
         let similar = similar_values(self.token.to_string().as_str(), Suppress::as_slice());
         if similar.is_empty() {
             None
@@ -60,6 +74,10 @@ impl PragmaInvalidSuppress {
         Self { token }
     }
 
+    fn is_error(&self) -> bool {
+        lints::is_error(&self.token.symbol().to_string())
+    }
+
     pub fn code(token: Token) -> Error {
         Error::Code(Arc::new(Self::new(Box::new(token))))
     }
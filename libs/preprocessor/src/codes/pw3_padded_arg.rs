@@ -1,4 +1,6 @@
-use hemtt_common::reporting::{Code, Diagnostic, Severity, Token};
+use std::rc::Rc;
+
+use hemtt_common::reporting::{Code, Diagnostic, Fix, Severity, Symbol, Token};
 
 #[allow(unused)]
 /// Unexpected token
@@ -7,6 +9,8 @@ pub struct PaddedArg {
     token: Box<Token>,
     /// The identifier of macro that was being padded
     ident: String,
+    /// Removes the padding, when it is only spaces and tabs
+    fix: Option<Fix>,
 }
 
 impl Code for PaddedArg {
@@ -30,13 +34,41 @@ impl Code for PaddedArg {
         Some("padding a macro argument is likely unintended".to_string())
     }
 
+    fn fix(&self) -> Option<Fix> {
+        self.fix.clone()
+    }
+
     fn expand_diagnostic(&self, diag: Diagnostic) -> Diagnostic {
         diag.with_note(format!("occured in: `{}`", self.ident))
     }
 }
 
 impl PaddedArg {
-    pub fn new(token: Box<Token>, ident: String) -> Self {
-        Self { token, ident }
+    pub fn new(token: Box<Token>, padding: &[&Rc<Token>], ident: String) -> Self {
+        Self {
+            token,
+            ident,
+            fix: Self::removal(padding),
+        }
+    }
+
+    fn removal(padding: &[&Rc<Token>]) -> Option<Fix> {
+        let first = padding.first()?.position();
+        let path = first.path();
+        if path.is_include() {
+            return None;
+        }
+        let mut end = first.start().0;
+        for token in padding {
+            let position = token.position();
+            if !matches!(token.symbol(), Symbol::Whitespace(_))
+                || position.path() != path
+                || position.start().0 != end
+            {
+                return None;
+            }
+            end = position.end().0;
+        }
+        Some(Fix::new(path.clone(), first.start().0..end, String::new()))
     }
 }
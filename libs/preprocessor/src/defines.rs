@@ -67,6 +67,13 @@ impl Defines {
         self.global.contains_key(key)
     }
 
+    /// Is the key an argument of the function macro being expanded
+    pub fn is_arg(&self, key: &str) -> bool {
+        self.stack
+            .last()
+            .is_some_and(|(name, args)| **name != *key && args.contains_key(key))
+    }
+
     pub fn get_with_gen(
         &mut self,
         key: &Rc<Token>,
@@ -52,6 +52,47 @@ impl Definition {
             _ => None,
         }
     }
+
+    #[must_use]
+    /// The arguments and body as they would be written after the name of the macro,
+    /// with whitespace collapsed so definitions can be compared
+    pub fn source(&self) -> String {
+        match self {
+            Self::Function(function) => format!(
+                "({}) {}",
+                function
+                    .args()
+                    .iter()
+                    .map(|arg| arg.to_source())
+                    .collect::<Vec<_>>()
+                    .join(","),
+                collapse(function.body())
+            )
+            .trim_end()
+            .to_string(),
+            Self::Value(body) => collapse(body),
+            Self::Unit | Self::Void => String::new(),
+        }
+    }
+}
+
+/// Joins tokens, with each run of whitespace, newlines and escapes replaced by a single space
+fn collapse(tokens: &[Rc<Token>]) -> String {
+    let mut out = String::new();
+    let mut space = false;
+    for token in tokens {
+        let symbol = token.symbol();
+        if symbol.is_whitespace() || symbol.is_newline() || symbol.is_escape() {
+            space = !out.is_empty();
+            continue;
+        }
+        if space {
+            out.push(' ');
+            space = false;
+        }
+        out.push_str(&token.to_source());
+    }
+    out
 }
 
 #[allow(clippy::module_name_repetitions)]
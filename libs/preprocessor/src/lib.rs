@@ -1,5 +1,6 @@
 //! HEMTT - Arma 3 Preprocessor
 
+mod cache;
 mod codes;
 mod defines;
 mod definition;
@@ -7,6 +8,9 @@ mod error;
 mod ifstate;
 mod parse;
 mod processor;
+mod trace;
 
+pub use cache::Cache;
 pub use error::Error;
 pub use processor::Processor;
+pub use trace::Expansion;
@@ -24,7 +24,18 @@ pub struct PreprocessorParser;
 /// If the file is invalid
 pub fn parse(path: &WorkspacePath) -> Result<Vec<Rc<Token>>, Error> {
     let source = path.read_to_string()?;
-    let pairs = PreprocessorParser::parse(Rule::file, &source)
+    str(&source, path)
+}
+
+/// Parse a string into tokens, with positions in `path`
+///
+/// # Errors
+/// If the string is invalid
+///
+/// # Panics
+/// If the string is invalid
+pub fn str(source: &str, path: &WorkspacePath) -> Result<Vec<Rc<Token>>, Error> {
+    let pairs = PreprocessorParser::parse(Rule::file, source)
         .map_err(|e| ParsingFailed::code(e, path.clone()))?;
     let mut tokens = Vec::new();
     let mut line = 1;
@@ -14,7 +14,7 @@ use crate::{
         pe9_function_call_argument_count::FunctionCallArgumentCount, pw3_padded_arg::PaddedArg,
     },
     definition::Definition,
-    Error,
+    Error, Expansion,
 };
 
 use super::{
@@ -205,6 +205,20 @@ impl Processor {
             buffer.push(Output::Direct(ident));
             return Ok(());
         };
+        self.record_usage(&source, &ident);
+        let is_arg = self.defines.is_arg(&ident_string);
+        let traced = self.trace.as_mut().filter(|_| !is_arg).map(|trace| {
+            trace.push(Expansion::new(
+                ident_string.clone(),
+                ident.position().clone(),
+                source.position().clone(),
+                self.trace_depth,
+            ));
+            trace.len() - 1
+        });
+        if traced.is_some() {
+            self.trace_depth += 1;
+        }
         match body {
             Definition::Function(function) => {
                 let Some(args) = self.call_read_args(callsite, pragma, stream)? else {
@@ -222,6 +236,13 @@ impl Processor {
                         &self.defines.clone(),
                     ));
                 }
+                if let (Some(trace), Some(index)) = (self.trace.as_mut(), traced) {
+                    trace[index].set_args(
+                        args.iter()
+                            .map(|arg| arg.iter().map(|t| t.to_string()).collect::<String>())
+                            .collect(),
+                    );
+                }
                 let mut arg_defines = HashMap::new();
                 for (arg, value) in function.args().iter().zip(args) {
                     if !pragma.is_suppressed(&Suppress::Pw3PaddedArg)
@@ -234,12 +255,22 @@ impl Processor {
                             .all(|s| !ident_string.starts_with(s))
                         })
                     {
-                        for token in [value.first(), value.last()] {
-                            if token.map_or(false, |t| t.symbol().is_whitespace()) {
+                        let leading = value
+                            .iter()
+                            .take_while(|t| t.symbol().is_whitespace())
+                            .collect::<Vec<_>>();
+                        let mut trailing = value
+                            .iter()
+                            .rev()
+                            .take_while(|t| t.symbol().is_whitespace())
+                            .collect::<Vec<_>>();
+                        trailing.reverse();
+                        for (token, padding) in [(value.first(), leading), (value.last(), trailing)]
+                        {
+                            if let Some(token) = token.filter(|_| !padding.is_empty()) {
                                 self.warnings.push(Arc::new(PaddedArg::new(
-                                    Box::new(
-                                        (**token.expect("token exists from map_or check")).clone(),
-                                    ),
+                                    Box::new((**token).clone()),
+                                    &padding,
                                     ident_string.clone(),
                                 )));
                             }
@@ -259,6 +290,7 @@ impl Processor {
                     &mut function.stream(),
                     &mut layer,
                 )?;
+                self.trace_output(traced, &layer);
                 buffer.push(Output::Macro(ident, layer));
                 self.defines.pop();
             }
@@ -273,9 +305,13 @@ impl Processor {
                     &mut body.into_iter().peekmore(),
                     &mut layer,
                 )?;
+                self.trace_output(traced, &layer);
                 buffer.push(Output::Macro(ident, layer));
             }
-            Definition::Void => return Ok(()),
+            Definition::Void => {
+                self.trace_output(traced, &[]);
+                return Ok(());
+            }
             Definition::Unit => {
                 #[allow(clippy::redundant_clone)] // behind hls feature flag
                 return Err(ExpectedFunctionOrValue::code(
@@ -289,20 +325,27 @@ impl Processor {
                 ));
             }
         };
-        #[cfg(feature = "lsp")]
-        self.usage.get_mut(source.position()).map_or_else(
-            || {
-                // println!("missing {:?}", ident.position());
-            },
-            |usage| {
-                usage.push(ident.position().clone());
-            },
-        );
-        #[cfg(feature = "lsp")]
-        self.declarations
-            .insert(ident.position().clone(), source.position().clone());
         Ok(())
     }
+
+    /// Records that a macro was used, by an expansion or an `#ifdef`
+    ///
+    /// Only macros defined by `#define` are recorded, not arguments or defines from the project
+    pub(crate) fn record_usage(&mut self, definition: &Token, usage: &Token) {
+        if let Some(usages) = self.usage.get_mut(definition.position()) {
+            usages.push(usage.position().clone());
+            self.declarations
+                .insert(usage.position().clone(), definition.position().clone());
+        }
+    }
+
+    /// Records the output of a traced expansion, and leaves its depth
+    fn trace_output(&mut self, traced: Option<usize>, layer: &[Output]) {
+        if let (Some(trace), Some(index)) = (self.trace.as_mut(), traced) {
+            self.trace_depth -= 1;
+            trace[index].set_output(layer.iter().map(ToString::to_string).collect());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -2,7 +2,7 @@ use std::{rc::Rc, sync::Arc};
 
 use hemtt_common::{
     position::Position,
-    reporting::{Output, Symbol, Token},
+    reporting::{MacroDefinition, Output, Symbol, Token},
 };
 use peekmore::{PeekMore, PeekMoreIterator};
 use tracing::debug;
@@ -103,7 +103,9 @@ impl Processor {
                 match command.to_string().as_str() {
                     "suppress" => {
                         let (code, scope) = self.read_pragma(&command, pragma, stream)?;
-                        pragma.suppress(&code, scope)?;
+                        if let Some(suppression) = pragma.suppress(&code, scope)? {
+                            self.suppressions.push(suppression);
+                        }
                     }
                     "flag" => {
                         let (code, scope) = self.read_pragma(&command, pragma, stream)?;
@@ -207,7 +209,13 @@ impl Processor {
         else {
             return Err(IncludeNotFound::code(path));
         };
-        let tokens = crate::parse::parse(&path)?;
+        if !self.includes.contains(&path) {
+            self.includes.push(path.clone());
+        }
+        let tokens = match &self.cache {
+            Some(cache) => cache.tokens(&path)?,
+            None => crate::parse::parse(&path)?,
+        };
         self.files.push(path);
         let mut stream = tokens.into_iter().peekmore();
         let ret = self.file(&mut pragma.child(), &mut stream, buffer);
@@ -233,11 +241,16 @@ impl Processor {
         if Defines::is_builtin(&ident_string) {
             return Err(ChangeBuiltin::code(ident.as_ref().clone()));
         }
+        let mut replaces = None;
         if let Some((original, _)) = self.defines.remove(&ident_string) {
-            self.warnings.push(Arc::new(RedefineMacro::new(
-                Box::new(ident.as_ref().clone()),
-                Box::new(original.as_ref().clone()),
-            )));
+            // macros defined by the project can be overridden without a warning
+            if !self.external.remove(&ident_string) {
+                replaces = Some(original.position().clone());
+                self.warnings.push(Arc::new(RedefineMacro::new(
+                    Box::new(ident.as_ref().clone()),
+                    Box::new(original.as_ref().clone()),
+                )));
+            }
         }
         let definition = match next.symbol() {
             Symbol::LeftParenthesis => Definition::Function({
@@ -257,8 +270,13 @@ impl Processor {
             Symbol::Newline | Symbol::Eoi => Definition::Unit,
             _ => Definition::Value(self.define_read_body(stream)),
         };
-        #[cfg(feature = "lsp")]
         self.usage.insert(ident.position().clone(), Vec::new());
+        self.definitions.push(MacroDefinition::new(
+            ident_string.clone(),
+            ident.position().clone(),
+            definition.source(),
+            replaces,
+        ));
         self.defines.insert(&ident_string, (ident, definition));
         Ok(())
     }
@@ -283,9 +301,15 @@ impl Processor {
         command: Rc<Token>,
         stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
     ) -> Result<(), Error> {
-        fn value(defines: &mut Defines, token: Rc<Token>) -> Result<(Vec<Rc<Token>>, bool), Error> {
-            if let Some((_, definition)) = defines.get_with_gen(&token, Some(token.position())) {
+        fn value(
+            processor: &mut Processor,
+            token: Rc<Token>,
+        ) -> Result<(Vec<Rc<Token>>, bool), Error> {
+            let defines = &mut processor.defines;
+            if let Some((source, definition)) = defines.get_with_gen(&token, Some(token.position()))
+            {
                 if let Definition::Value(tokens) = definition {
+                    processor.record_usage(&source, &token);
                     return Ok((tokens, true));
                 }
                 return Err(IfUnitOrFunction::code(
@@ -308,7 +332,7 @@ impl Processor {
             }
             return Err(IfHasInclude::code(left.as_ref().clone()));
         }
-        let (left, left_defined) = value(&mut self.defines, left)?;
+        let (left, left_defined) = value(self, left)?;
         self.skip_whitespace(stream, None);
         let mut operators = Vec::with_capacity(2);
         let (right, right_defined) = if stream.peek().map(|t| t.symbol()) == Some(&Symbol::Newline)
@@ -345,7 +369,7 @@ impl Processor {
                         .clone(),
                 ));
             };
-            value(&mut self.defines, right)?
+            value(self, right)?
         };
         let operator = operators.iter().map(|t| t.to_string()).collect::<String>();
         let left_string = left.iter().map(|t| t.to_string()).collect::<String>();
@@ -398,6 +422,9 @@ impl Processor {
             return Err(ExpectedIdent::code(ident.as_ref().clone()));
         }
         let ident_string = ident.symbol().to_string();
+        if let Some((definition, _)) = self.defines.get_readonly(&ident_string) {
+            self.record_usage(&definition, &ident);
+        }
         self.ifstates
             .push_if(command, self.defines.contains_key(&ident_string) == outcome);
         Self::expect_nothing_to_newline(stream)
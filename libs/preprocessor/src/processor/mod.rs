@@ -1,10 +1,12 @@
-#[cfg(feature = "lsp")]
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
 use std::rc::Rc;
 use std::sync::Arc;
 
-use hemtt_common::position::Position;
-use hemtt_common::reporting::{Code, Output, Processed, Symbol, Token};
+use hemtt_common::position::{LineCol, Position};
+use hemtt_common::reporting::{
+    Code, MacroDefinition, Output, Processed, Suppression, Symbol, Token,
+};
 use hemtt_common::workspace::WorkspacePath;
 use peekmore::{PeekMore, PeekMoreIterator};
 
@@ -13,8 +15,9 @@ use crate::codes::pe2_unexpected_eof::UnexpectedEOF;
 use crate::codes::pe3_expected_ident::ExpectedIdent;
 use crate::codes::pw2_invalid_config_case::InvalidConfigCase;
 use crate::defines::Defines;
+use crate::definition::Definition;
 use crate::ifstate::IfStates;
-use crate::Error;
+use crate::{Cache, Error, Expansion};
 
 use self::pragma::Pragma;
 
@@ -33,21 +36,40 @@ pub struct Processor {
 
     pub(crate) token_count: usize,
 
-    #[cfg(feature = "lsp")]
     /// Map of token usage to definition
     /// (token, definition)
     pub(crate) declarations: HashMap<Position, Position>,
 
-    #[cfg(feature = "lsp")]
     /// Map of token definition to usage
     /// (definition, usages)
     pub(crate) usage: HashMap<Position, Vec<Position>>,
 
+    /// Macros defined by `#define`
+    pub(crate) definitions: Vec<MacroDefinition>,
+
+    /// Files included by `#include`
+    pub(crate) includes: Vec<WorkspacePath>,
+
     /// Warnings
     pub(crate) warnings: Vec<Arc<dyn Code>>,
 
+    /// Codes suppressed by `#pragma hemtt suppress`
+    pub(crate) suppressions: Vec<Suppression>,
+
     /// The preprocessor was able to run checks, but the output should not be rapified
     pub(crate) no_rapify: bool,
+
+    /// Macro expansions, when tracing
+    pub(crate) trace: Option<Vec<Expansion>>,
+
+    /// How many expansions the current one is nested in
+    pub(crate) trace_depth: usize,
+
+    /// Macros defined before the file, which it can redefine without a warning
+    pub(crate) external: HashSet<String>,
+
+    /// Tokenized include files shared with other runs
+    pub(crate) cache: Option<Arc<Cache>>,
 }
 
 impl Processor {
@@ -62,35 +84,105 @@ impl Processor {
     /// # Errors
     /// See [`Error`]
     pub fn run(path: &WorkspacePath) -> Result<Processed, Error> {
-        let mut processor = Self::default();
+        Self::default().process(path)
+    }
+
+    /// Preprocess a file, and record every macro expansion
+    ///
+    /// Expansions are in the order they started, and nested expansions follow the one they are part of
+    ///
+    /// # Errors
+    /// See [`Error`]
+    pub fn run_with_trace(path: &WorkspacePath) -> Result<(Processed, Vec<Expansion>), Error> {
+        let mut processor = Self {
+            trace: Some(Vec::new()),
+            ..Default::default()
+        };
+        let processed = processor.process(path)?;
+        Ok((processed, processor.trace.take().unwrap_or_default()))
+    }
+
+    /// Preprocess a file, with macros defined before it, such as those from the project config
+    ///
+    /// An empty value defines a macro that can only be checked with `#ifdef`.
+    /// Included files are tokenized through `cache`, which can be shared by every file of a build
+    ///
+    /// # Errors
+    /// See [`Error`]
+    pub fn run_with_defines<S: BuildHasher>(
+        path: &WorkspacePath,
+        defines: &HashMap<String, String, S>,
+        cache: &Arc<Cache>,
+    ) -> Result<Processed, Error> {
+        let mut processor = Self {
+            cache: Some(cache.clone()),
+            ..Default::default()
+        };
+        processor.define_external(path, defines)?;
+        processor.process(path)
+    }
+
+    fn define_external<S: BuildHasher>(
+        &mut self,
+        path: &WorkspacePath,
+        defines: &HashMap<String, String, S>,
+    ) -> Result<(), Error> {
+        let start = LineCol(0, (1, 0));
+        for (name, value) in defines {
+            // the value has no file of its own, its tokens point at the start of the root file
+            let body = crate::parse::str(value, path)?
+                .into_iter()
+                .filter(|token| !token.symbol().is_eoi())
+                .map(|token| {
+                    Rc::new(Token::new(
+                        token.symbol().clone(),
+                        Position::new(start, start, path.clone()),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            let ident = Rc::new(Token::new(
+                Symbol::Word(name.clone()),
+                Position::new(start, start, path.clone()),
+            ));
+            let definition = if body.iter().all(|token| token.symbol().is_whitespace()) {
+                Definition::Unit
+            } else {
+                Definition::Value(body)
+            };
+            self.defines.insert(name, (ident, definition));
+            self.external.insert(name.clone());
+        }
+        Ok(())
+    }
 
-        processor.files.push(path.clone());
+    fn process(&mut self, path: &WorkspacePath) -> Result<Processed, Error> {
+        self.files.push(path.clone());
 
         let tokens = crate::parse::parse(path)?;
         let mut pragma = Pragma::root();
         let mut buffer = Vec::with_capacity(tokens.len());
         let mut stream = tokens.into_iter().peekmore();
 
-        processor.file(&mut pragma, &mut stream, &mut buffer)?;
+        self.file(&mut pragma, &mut stream, &mut buffer)?;
 
-        if let Some(state) = processor.ifstates.pop() {
+        if let Some(state) = self.ifstates.pop() {
             return Err(EoiIfState::code(state.token().as_ref().clone()));
         }
 
         if path.filename() == "Config.cpp" {
-            processor
-                .warnings
+            self.warnings
                 .push(Arc::new(InvalidConfigCase::new(path.clone())));
         }
 
         Processed::new(
             buffer,
-            #[cfg(feature = "lsp")]
-            processor.usage,
-            #[cfg(feature = "lsp")]
-            processor.declarations,
-            processor.warnings,
-            processor.no_rapify,
+            std::mem::take(&mut self.usage),
+            std::mem::take(&mut self.declarations),
+            std::mem::take(&mut self.definitions),
+            std::mem::take(&mut self.includes),
+            std::mem::take(&mut self.warnings),
+            std::mem::take(&mut self.suppressions),
+            self.no_rapify,
         )
         .map_err(Into::into)
     }
@@ -1,6 +1,9 @@
 use std::{collections::HashMap, rc::Rc};
 
-use hemtt_common::reporting::Token;
+use hemtt_common::{
+    project::lints,
+    reporting::{Suppression, Token},
+};
 
 use crate::{
     codes::{
@@ -58,18 +61,37 @@ impl Pragma {
         self.suppress.contains_key(code)
     }
 
-    pub fn suppress(&mut self, token: &Rc<Token>, scope: Scope) -> Result<(), Error> {
+    /// Suppress a warning for a scope
+    ///
+    /// Named warnings, such as `pw3_padded_arg`, are suppressed while preprocessing.
+    /// Any other code, such as `PW1` or `SAA7`, returns a [`Suppression`]
+    /// that is checked where the code is reported.
+    pub fn suppress(
+        &mut self,
+        token: &Rc<Token>,
+        scope: Scope,
+    ) -> Result<Option<Suppression>, Error> {
         let code = token.symbol().to_string();
         let Ok(suppress) = Suppress::try_from(code.as_str()) else {
-            return Err(PragmaInvalidSuppress::code((**token).clone()));
+            if !is_code(&code) || lints::is_error(&code) {
+                return Err(PragmaInvalidSuppress::code((**token).clone()));
+            }
+            let position = token.position();
+            // positions start at line 1, so this is the line after the pragma
+            let line = position.start().line();
+            return Ok(Some(match scope {
+                Scope::Line => Suppression::line(code, position.path().clone(), line),
+                Scope::File => Suppression::file(code, position.path().clone(), line),
+                Scope::Config => Suppression::config(code),
+            }));
         };
         if let Some(existing) = self.suppress.get(&suppress) {
             if *existing as u8 > scope as u8 {
-                return Ok(());
+                return Ok(None);
             }
         }
         self.suppress.insert(suppress, scope);
-        Ok(())
+        Ok(None)
     }
 
     pub fn is_flagged(&self, code: &Flag) -> bool {
@@ -135,6 +157,12 @@ impl TryFrom<&str> for Flag {
     }
 }
 
+/// Is the word a code identifier, such as `PW1` or `SAA7`
+fn is_code(word: &str) -> bool {
+    let number = word.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    number.len() < word.len() && !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Suppress {
     Pw3PaddedArg,
@@ -0,0 +1,85 @@
+//! Tracing of macro expansions
+
+use hemtt_common::position::Position;
+
+#[derive(Debug, Clone)]
+/// A macro that was expanded while preprocessing
+pub struct Expansion {
+    name: String,
+    args: Option<Vec<String>>,
+    callsite: Position,
+    definition: Position,
+    depth: usize,
+    output: String,
+}
+
+impl Expansion {
+    pub(crate) const fn new(
+        name: String,
+        callsite: Position,
+        definition: Position,
+        depth: usize,
+    ) -> Self {
+        Self {
+            name,
+            args: None,
+            callsite,
+            definition,
+            depth,
+            output: String::new(),
+        }
+    }
+
+    pub(crate) fn set_args(&mut self, args: Vec<String>) {
+        self.args = Some(args);
+    }
+
+    pub(crate) fn set_output(&mut self, output: String) {
+        self.output = output;
+    }
+
+    #[must_use]
+    /// The name of the macro
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    /// The arguments of a function macro, after their own macros were expanded
+    pub fn args(&self) -> Option<&[String]> {
+        self.args.as_deref()
+    }
+
+    #[must_use]
+    /// Where the macro was used
+    pub const fn callsite(&self) -> &Position {
+        &self.callsite
+    }
+
+    #[must_use]
+    /// Where the macro was defined
+    pub const fn definition(&self) -> &Position {
+        &self.definition
+    }
+
+    #[must_use]
+    /// How many expansions this one is nested in, 0 for a macro used directly in a file
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    #[must_use]
+    /// The text the macro expanded to
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    #[must_use]
+    /// The macro as it was called, such as `GVAR(heal)`
+    pub fn call(&self) -> String {
+        self.args.as_ref().map_or_else(
+            || self.name.clone(),
+            |args| format!("{}({})", self.name, args.join(",")),
+        )
+    }
+}
@@ -0,0 +1,52 @@
+use std::{collections::HashMap, sync::Arc};
+
+use hemtt_common::workspace::LayerType;
+use hemtt_preprocessor::{Cache, Processor};
+
+#[test]
+fn defines() {
+    let folder = std::path::PathBuf::from("tests/defines");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.hpp").unwrap();
+    let defines = HashMap::from([
+        ("DEBUG_MODE_FULL".to_string(), String::new()),
+        ("MAX_UNITS".to_string(), "40".to_string()),
+        ("NAME".to_string(), "\"abe\"".to_string()),
+    ]);
+    let processed =
+        Processor::run_with_defines(&source, &defines, &Arc::new(Cache::new())).unwrap();
+    assert_eq!(
+        processed
+            .as_str()
+            .replace('\r', "")
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "class settings {\n    debug = 1;\n    units = 10;\n    name = \"abe\";\n};"
+    );
+    // the file's own define replaces the project's without a warning
+    assert!(processed.warnings().is_empty());
+}
+
+#[test]
+fn builtin_fallback() {
+    let folder = std::path::PathBuf::from("tests/defines");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("fallback.hpp").unwrap();
+    let version = |defines: &HashMap<String, String>| {
+        let processed =
+            Processor::run_with_defines(&source, defines, &Arc::new(Cache::new())).unwrap();
+        processed.as_str().trim().to_string()
+    };
+    let defines = HashMap::from([("__HEMTT_PROJECT_VERSION__".to_string(), "1.2.3".to_string())]);
+    assert_eq!(version(&defines), "version = \"1.2.3\";");
+    // a project can define the builtin itself when HEMTT does not
+    assert_eq!(version(&HashMap::new()), "version = \"0.0.0\";");
+}
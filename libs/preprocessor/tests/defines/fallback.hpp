@@ -0,0 +1,5 @@
+#ifndef __HEMTT_PROJECT_VERSION__
+    #define __HEMTT_PROJECT_VERSION__ 0.0.0
+#endif
+#define QUOTE(var) #var
+version = QUOTE(__HEMTT_PROJECT_VERSION__);
@@ -0,0 +1,12 @@
+#define MAX_UNITS 10
+
+class settings {
+#ifdef DEBUG_MODE_FULL
+    debug = 1;
+#endif
+#ifdef HEMTT_RELEASE
+    release = 1;
+#endif
+    units = MAX_UNITS;
+    name = NAME;
+};
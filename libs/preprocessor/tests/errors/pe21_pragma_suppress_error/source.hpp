@@ -0,0 +1 @@
+#pragma hemtt suppress PE12 file
@@ -0,0 +1,34 @@
+use hemtt_common::{reporting::Fix, workspace::LayerType};
+use hemtt_preprocessor::Processor;
+
+#[test]
+fn fix() {
+    let folder = std::path::PathBuf::from("tests/fix");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.hpp").unwrap();
+    let content = source.read_to_string().unwrap();
+    let processed = Processor::run(&source).unwrap();
+    // padding is removed from the source, not the output
+    let fixes = processed
+        .warnings()
+        .iter()
+        .filter_map(|warning| warning.fix())
+        .collect::<Vec<_>>();
+    assert_eq!(fixes.len(), 2);
+    assert!(fixes.iter().all(|fix| fix.replacement().is_empty()));
+    assert_eq!(&content[fixes[0].span().clone()], "  ");
+    assert_eq!(&content[fixes[1].span().clone()], " ");
+    assert_eq!(fixes[1].span().end, content.find(");").unwrap());
+    // text from a macro can not be fixed
+    let output = processed.as_str();
+    let word = output.find("word").unwrap();
+    assert_eq!(Fix::for_processed(word..word + 4, "x", &processed), None);
+    // text from the source maps back to it
+    let text = output.find("text").unwrap();
+    let fix = Fix::for_processed(text..text + 4, "x", &processed).unwrap();
+    assert_eq!(fix.file(), &source);
+    assert_eq!(&content[fix.span().clone()], "text");
+}
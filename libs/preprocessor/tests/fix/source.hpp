@@ -0,0 +1,5 @@
+#define ADD(a,b) a + b
+#define WORD word
+value = ADD(  1,2 );
+other = WORD;
+plain = text;
@@ -0,0 +1,22 @@
+use hemtt_common::workspace::LayerType;
+use hemtt_preprocessor::Processor;
+
+#[test]
+fn includes() {
+    let folder = std::path::PathBuf::from("tests/includes");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.sqf").unwrap();
+    let processed = Processor::run(&source).unwrap();
+    // headers that only define or check macros add nothing to the output, but are still included
+    assert_eq!(
+        processed
+            .includes()
+            .iter()
+            .map(hemtt_common::workspace::WorkspacePath::as_str)
+            .collect::<Vec<_>>(),
+        vec!["/defines.hpp", "/checks.hpp", "/nested.hpp"]
+    );
+}
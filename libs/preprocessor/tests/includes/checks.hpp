@@ -0,0 +1,3 @@
+#ifdef VALUE
+#include "nested.hpp"
+#endif
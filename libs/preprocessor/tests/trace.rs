@@ -0,0 +1,39 @@
+use hemtt_common::workspace::LayerType;
+use hemtt_preprocessor::Processor;
+
+#[test]
+fn trace() {
+    let folder = std::path::PathBuf::from("tests/trace");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.hpp").unwrap();
+    let (processed, expansions) = Processor::run_with_trace(&source).unwrap();
+    assert_eq!(
+        processed.as_str().replace('\r', "").trim(),
+        "class abe_main {\n    name = \"abe_main_heal\";\n};"
+    );
+    let steps = expansions
+        .iter()
+        .map(|e| (e.depth(), e.call(), e.output().to_string()))
+        .collect::<Vec<_>>();
+    let expected = [
+        (0, "ADDON", "abe_main"),
+        (1, "DOUBLES(abe,main)", "abe_main"),
+        (2, "PREFIX", "abe"),
+        (2, "COMPONENT", "main"),
+        (0, "QGVAR(heal)", "\"abe_main_heal\""),
+        (1, "QUOTE(abe_main_heal)", "\"abe_main_heal\""),
+        (2, "GVAR(heal)", "abe_main_heal"),
+        (3, "DOUBLES(abe_main,heal)", "abe_main_heal"),
+        (4, "ADDON", "abe_main"),
+        (5, "DOUBLES(abe,main)", "abe_main"),
+        (6, "PREFIX", "abe"),
+        (6, "COMPONENT", "main"),
+    ]
+    .map(|(depth, call, output)| (depth, call.to_string(), output.to_string()));
+    assert_eq!(steps, expected);
+    assert_eq!(expansions[4].callsite().start().line(), 3);
+    assert_eq!(expansions[4].definition().path().as_str(), "/macros.hpp");
+}
@@ -0,0 +1,4 @@
+#include "macros.hpp"
+class ADDON {
+    name = QGVAR(heal);
+};
@@ -0,0 +1,35 @@
+use hemtt_common::workspace::LayerType;
+use hemtt_preprocessor::Processor;
+
+#[test]
+fn usage() {
+    let folder = std::path::PathBuf::from("tests/usage");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.hpp").unwrap();
+    let processed = Processor::run(&source).unwrap();
+    let definitions = processed.definitions();
+    assert_eq!(
+        definitions
+            .iter()
+            .map(hemtt_common::reporting::MacroDefinition::name)
+            .collect::<Vec<_>>(),
+        vec!["USED", "UNUSED", "CHECKED", "USED", "LOCAL"]
+    );
+    assert_eq!(definitions[3].body(), "3");
+    // the second `USED` replaces the first
+    assert_eq!(definitions[3].replaces(), Some(definitions[0].position()));
+    let used = |index: usize| {
+        processed
+            .usage()
+            .get(definitions[index].position())
+            .is_some_and(|usages| !usages.is_empty())
+    };
+    assert!(!used(0));
+    assert!(!used(1));
+    assert!(used(2));
+    assert!(used(3));
+    assert!(!used(4));
+}
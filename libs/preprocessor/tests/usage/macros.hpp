@@ -0,0 +1,4 @@
+#define USED 1
+#define UNUSED 2
+#define CHECKED
+#define USED 3
@@ -0,0 +1,8 @@
+#include "macros.hpp"
+
+#define LOCAL 1
+
+value = USED;
+#ifdef CHECKED
+checked = 1;
+#endif
@@ -62,3 +62,15 @@ fn check(dir: &str) {
 
 bootstrap!(pw1_redefine);
 bootstrap!(pw3_padded_arg);
+
+#[test]
+fn pre_warning_suppress() {
+    let folder = std::path::PathBuf::from(ROOT).join("suppress");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.hpp").unwrap();
+    let processed = Processor::run(&source).unwrap();
+    assert!(processed.warnings().is_empty());
+}
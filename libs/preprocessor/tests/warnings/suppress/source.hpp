@@ -0,0 +1,11 @@
+#define A 1
+#pragma hemtt suppress PW1
+#define A 2
+
+#define B 1
+// hemtt-ignore PW1
+#define B 2
+
+#pragma hemtt suppress pw1 file
+#define C 1
+#define C 2
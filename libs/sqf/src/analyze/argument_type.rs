@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use hemtt_common::{
+    project::sqf::ArgumentType,
+    reporting::{Code, Processed},
+};
+
+use crate::{
+    analyze::codes::saw12_argument_type::ArgumentTypeMismatch, parser::database::Database,
+    BinaryCommand, Expression, Statements, UnaryCommand,
+};
+
+/// Checks literal arguments of commands declared in project.toml against their declared types
+pub fn argument_type(
+    statements: &Statements,
+    processed: &Processed,
+    database: &Database,
+) -> Vec<Arc<dyn Code>> {
+    let mut warnings: Vec<Arc<dyn Code>> = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            warnings.extend(check_expression(expression, processed, database));
+        }
+    }
+    warnings
+}
+
+fn check_expression(
+    expression: &Expression,
+    processed: &Processed,
+    database: &Database,
+) -> Vec<Arc<dyn Code>> {
+    let (name, left, right) = match expression {
+        Expression::UnaryCommand(UnaryCommand::Named(name), right, _) => (name, None, right),
+        Expression::BinaryCommand(BinaryCommand::Named(name), left, right, _) => {
+            (name, Some(left), right)
+        }
+        _ => return Vec::new(),
+    };
+    let Some(command) = database.custom_command(name) else {
+        return Vec::new();
+    };
+    let mut warnings: Vec<Arc<dyn Code>> = Vec::new();
+    for (argument, expected, side) in [
+        (left, command.left(), "left"),
+        (Some(right), command.right(), "right"),
+    ] {
+        let (Some(argument), Some(expected)) = (argument, expected) else {
+            continue;
+        };
+        let Some(found) = literal_type(argument) else {
+            continue;
+        };
+        if expected != ArgumentType::Anything && expected != found {
+            warnings.push(Arc::new(ArgumentTypeMismatch::new(
+                argument.span(),
+                command.name().to_string(),
+                side,
+                expected,
+                found,
+                processed,
+            )));
+        }
+    }
+    warnings
+}
+
+/// The type of a literal value, `None` for expressions whose type is only known at runtime
+const fn literal_type(expression: &Expression) -> Option<ArgumentType> {
+    match expression {
+        Expression::String(_, _) => Some(ArgumentType::String),
+        Expression::Number(_, _) => Some(ArgumentType::Number),
+        Expression::Boolean(_, _) => Some(ArgumentType::Boolean),
+        Expression::Array(_, _) => Some(ArgumentType::Array),
+        Expression::Code(_) => Some(ArgumentType::Code),
+        _ => None,
+    }
+}
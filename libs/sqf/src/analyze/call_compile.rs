@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{
+    analyze::codes::saa6_call_compile_format::CallCompileFormat, BinaryCommand, Expression,
+    Statements, UnaryCommand,
+};
+
+pub fn call_compile(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let mut advice: Vec<Arc<dyn Code>> = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            advice.extend(check_expression(expression, processed));
+        }
+    }
+    advice
+}
+
+fn check_expression(expression: &Expression, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let compiled = match expression {
+        Expression::UnaryCommand(UnaryCommand::Named(name), compiled, _)
+        | Expression::BinaryCommand(BinaryCommand::Named(name), _, compiled, _)
+            if name.eq_ignore_ascii_case("call") =>
+        {
+            compiled
+        }
+        _ => return Vec::new(),
+    };
+    let Expression::UnaryCommand(UnaryCommand::Named(compile), format, _) = &**compiled else {
+        return Vec::new();
+    };
+    if !compile.eq_ignore_ascii_case("compile") {
+        return Vec::new();
+    }
+    let Expression::UnaryCommand(UnaryCommand::Named(name), arguments, _) = &**format else {
+        return Vec::new();
+    };
+    if !name.eq_ignore_ascii_case("format") {
+        return Vec::new();
+    }
+    let Expression::Array(arguments, _) = &**arguments else {
+        return Vec::new();
+    };
+    let Some(Expression::String(template, _)) = arguments.first() else {
+        return Vec::new();
+    };
+    // only a variable name, such as `%1_fnc_%2`, can be read instead of compiled
+    if template.is_empty()
+        || !template
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '%')
+    {
+        return Vec::new();
+    }
+    vec![Arc::new(CallCompileFormat::new(
+        compiled.full_span(),
+        format.source(),
+        processed,
+    ))]
+}
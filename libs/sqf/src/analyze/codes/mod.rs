@@ -1,7 +1,25 @@
 pub mod sae1_require_version;
 
+pub mod saw10_unreachable;
+pub mod saw11_null_compare;
+pub mod saw12_argument_type;
+pub mod saw1_deprecated;
+pub mod saw2_suspend_unscheduled;
+pub mod saw3_params_invalid;
+pub mod saw4_params_unused;
+pub mod saw5_params_arity;
+pub mod saw6_format_missing_argument;
+pub mod saw7_format_unused_argument;
+pub mod saw8_format_invalid_placeholder;
+pub mod saw9_remote_exec_dynamic;
+
+pub mod saa10_count_empty;
 pub mod saa1_if_assign;
 pub mod saa2_find_in_str;
 pub mod saa3_typename;
 pub mod saa4_str_format;
 pub mod saa5_select_parse_number;
+pub mod saa6_call_compile_format;
+pub mod saa7_for_count;
+pub mod saa8_if_bool;
+pub mod saa9_is_nil_local;
@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct CountEmpty {
+    span: Range<usize>,
+    array: String,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for CountEmpty {
+    fn ident(&self) -> &'static str {
+        "SAA10"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Help
+    }
+
+    fn message(&self) -> String {
+        String::from("`count` is used to check for an empty array")
+    }
+
+    fn label_message(&self) -> String {
+        String::from("use `isEqualTo []`")
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        Some(format!("{} isEqualTo []", self.array))
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(String::from(
+            "`isEqualTo []` does not need to count the elements",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl CountEmpty {
+    #[must_use]
+    pub fn new(span: Range<usize>, array: String, processed: &Processed) -> Self {
+        Self {
+            span,
+            array,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct CallCompileFormat {
+    span: Range<usize>,
+    format: String,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for CallCompileFormat {
+    fn ident(&self) -> &'static str {
+        "SAA6"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Help
+    }
+
+    fn message(&self) -> String {
+        String::from("`compile format` only builds a variable name")
+    }
+
+    fn label_message(&self) -> String {
+        String::from("compiles a variable name")
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        Some(format!("(missionNamespace getVariable {})", self.format))
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(String::from(
+            "compiling a string is slow, and the variable can be read directly",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl CallCompileFormat {
+    #[must_use]
+    pub fn new(span: Range<usize>, format: String, processed: &Processed) -> Self {
+        Self {
+            span,
+            format,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
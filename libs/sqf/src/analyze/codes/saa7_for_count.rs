@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct ForCount {
+    span: Range<usize>,
+    array: String,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for ForCount {
+    fn ident(&self) -> &'static str {
+        "SAA7"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Help
+    }
+
+    fn message(&self) -> String {
+        format!("`for` loop over each index of `{}`", self.array)
+    }
+
+    fn label_message(&self) -> String {
+        String::from("use `forEach`")
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        Some(format!("{{ ... }} forEach {}", self.array))
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(String::from(
+            "inside `forEach`, `_x` is the element and `_forEachIndex` is its index",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl ForCount {
+    #[must_use]
+    pub fn new(span: Range<usize>, array: String, processed: &Processed) -> Self {
+        Self {
+            span,
+            array,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,69 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct IfBool {
+    span: Range<usize>,
+    condition: String,
+    negated: bool,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for IfBool {
+    fn ident(&self) -> &'static str {
+        "SAA8"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Help
+    }
+
+    fn message(&self) -> String {
+        String::from("`if` only returns `true` or `false`")
+    }
+
+    fn label_message(&self) -> String {
+        if self.negated {
+            String::from("use the negated condition")
+        } else {
+            String::from("use the condition")
+        }
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        Some(if self.negated {
+            format!("!({})", self.condition)
+        } else {
+            self.condition.clone()
+        })
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl IfBool {
+    #[must_use]
+    pub fn new(
+        span: Range<usize>,
+        condition: String,
+        negated: bool,
+        processed: &Processed,
+    ) -> Self {
+        Self {
+            span,
+            condition,
+            negated,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,72 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Fix, Processed, Severity};
+
+pub struct IsNilLocal {
+    span: Range<usize>,
+    variable: String,
+
+    diagnostic: Option<Diagnostic>,
+    fix: Option<Fix>,
+}
+
+impl Code for IsNilLocal {
+    fn ident(&self) -> &'static str {
+        "SAA9"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Help
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`isNil` looks up the local variable `{}` by name",
+            self.variable
+        )
+    }
+
+    fn label_message(&self) -> String {
+        String::from("local variable as a string")
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        Some(format!("isNil {{{}}}", self.variable))
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(String::from(
+            "a name in a string is not checked for typos, or renamed with the variable",
+        ))
+    }
+
+    fn fix(&self) -> Option<Fix> {
+        self.fix.clone()
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl IsNilLocal {
+    #[must_use]
+    pub fn new(span: Range<usize>, variable: String, processed: &Processed) -> Self {
+        Self {
+            span,
+            variable,
+
+            diagnostic: None,
+            fix: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self.fix = self
+            .suggestion()
+            .and_then(|suggestion| Fix::for_processed(self.span.clone(), suggestion, processed));
+        self
+    }
+}
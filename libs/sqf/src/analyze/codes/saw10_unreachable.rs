@@ -0,0 +1,74 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Label, Processed, Severity};
+
+pub struct Unreachable {
+    span: Range<usize>,
+    exit: (String, Range<usize>),
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for Unreachable {
+    fn ident(&self) -> &'static str {
+        "SAW10"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        String::from("unreachable code")
+    }
+
+    fn label_message(&self) -> String {
+        String::from("never runs")
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "remove the code, or the statement that exits before it",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl Unreachable {
+    #[must_use]
+    pub fn new(span: Range<usize>, exit: (String, Range<usize>), processed: &Processed) -> Self {
+        Self {
+            span,
+            exit,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        let (Some(start), Some(end)) = (
+            processed.mapping(self.exit.1.start),
+            processed.mapping(self.exit.1.end),
+        ) else {
+            return self;
+        };
+        let Some(file) = processed.source(start.source()) else {
+            return self;
+        };
+        if let Some(diag) = &mut self.diagnostic {
+            diag.labels.push(
+                Label::secondary(
+                    file.0.clone(),
+                    start.original_column()..end.original_column(),
+                )
+                .with_message(format!("`{}` always exits the scope", self.exit.0)),
+            );
+        }
+        self
+    }
+}
@@ -0,0 +1,77 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct NullCompare {
+    span: Range<usize>,
+    null: String,
+    value: String,
+    negated: bool,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for NullCompare {
+    fn ident(&self) -> &'static str {
+        "SAW11"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "comparing to `{}` with `{}` is always {}",
+            self.null,
+            if self.negated { "!=" } else { "==" },
+            if self.negated { "true" } else { "false" },
+        )
+    }
+
+    fn label_message(&self) -> String {
+        String::from("use `isNull`")
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        Some(if self.negated {
+            format!("!isNull {}", self.value)
+        } else {
+            format!("isNull {}", self.value)
+        })
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(format!("`{0} == {0}` is false", self.null))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl NullCompare {
+    #[must_use]
+    pub fn new(
+        span: Range<usize>,
+        null: String,
+        value: String,
+        negated: bool,
+        processed: &Processed,
+    ) -> Self {
+        Self {
+            span,
+            null,
+            value,
+            negated,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,75 @@
+use std::ops::Range;
+
+use hemtt_common::{
+    project::sqf::ArgumentType,
+    reporting::{Code, Diagnostic, Processed, Severity},
+};
+
+pub struct ArgumentTypeMismatch {
+    span: Range<usize>,
+    command: String,
+    side: &'static str,
+    expected: ArgumentType,
+    found: ArgumentType,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for ArgumentTypeMismatch {
+    fn ident(&self) -> &'static str {
+        "SAW12"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`{}` expects {} on the {}, not {}",
+            self.command, self.expected, self.side, self.found
+        )
+    }
+
+    fn label_message(&self) -> String {
+        format!("{} argument", self.found)
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(String::from(
+            "the argument types of this command are declared in project.toml",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl ArgumentTypeMismatch {
+    #[must_use]
+    pub fn new(
+        span: Range<usize>,
+        command: String,
+        side: &'static str,
+        expected: ArgumentType,
+        found: ArgumentType,
+        processed: &Processed,
+    ) -> Self {
+        Self {
+            span,
+            command,
+            side,
+            expected,
+            found,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
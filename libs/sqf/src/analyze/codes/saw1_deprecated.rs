@@ -0,0 +1,73 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct Deprecated {
+    span: Range<usize>,
+    command: String,
+    replacement: Option<String>,
+    reason: Option<String>,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for Deprecated {
+    fn ident(&self) -> &'static str {
+        "SAW1"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!("`{}` is deprecated", self.command)
+    }
+
+    fn label_message(&self) -> String {
+        self.replacement.as_ref().map_or_else(
+            || String::from("deprecated"),
+            |replacement| format!("use `{replacement}` instead"),
+        )
+    }
+
+    fn note(&self) -> Option<String> {
+        self.reason.clone()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "deprecated commands can be allowed with `lints.sqf.deprecated` in project.toml",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl Deprecated {
+    #[must_use]
+    pub fn new(
+        span: Range<usize>,
+        command: String,
+        replacement: Option<String>,
+        reason: Option<String>,
+        processed: &Processed,
+    ) -> Self {
+        Self {
+            span,
+            command,
+            replacement,
+            reason,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
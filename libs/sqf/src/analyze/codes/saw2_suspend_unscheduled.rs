@@ -0,0 +1,95 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Label, Processed, Severity};
+
+pub struct SuspendUnscheduled {
+    span: Range<usize>,
+    command: String,
+    context: Option<Range<usize>>,
+    reason: String,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for SuspendUnscheduled {
+    fn ident(&self) -> &'static str {
+        "SAW2"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!("`{}` can not suspend in unscheduled code", self.command)
+    }
+
+    fn label_message(&self) -> String {
+        String::from("suspends execution")
+    }
+
+    fn note(&self) -> Option<String> {
+        if self.context.is_some() {
+            None
+        } else {
+            Some(self.reason.clone())
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "use `spawn`, or `CBA_fnc_waitAndExecute` / `CBA_fnc_waitUntilAndExecute` to delay unscheduled code",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl SuspendUnscheduled {
+    #[must_use]
+    pub fn new(
+        span: Range<usize>,
+        command: String,
+        context: Option<Range<usize>>,
+        reason: String,
+        processed: &Processed,
+    ) -> Self {
+        Self {
+            span,
+            command,
+            context,
+            reason,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        let Some(context) = &self.context else {
+            return self;
+        };
+        let (Some(start), Some(end)) = (
+            processed.mapping(context.start),
+            processed.mapping(context.end),
+        ) else {
+            return self;
+        };
+        let Some(file) = processed.source(start.source()) else {
+            return self;
+        };
+        if let Some(diag) = &mut self.diagnostic {
+            diag.labels.push(
+                Label::secondary(
+                    file.0.clone(),
+                    start.original_column()..end.original_column(),
+                )
+                .with_message(self.reason.clone()),
+            );
+        }
+        self
+    }
+}
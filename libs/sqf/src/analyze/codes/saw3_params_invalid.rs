@@ -0,0 +1,64 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct ParamsInvalid {
+    span: Range<usize>,
+    problem: String,
+    label: String,
+    help: Option<String>,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for ParamsInvalid {
+    fn ident(&self) -> &'static str {
+        "SAW3"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!("invalid `params` definition: {}", self.problem)
+    }
+
+    fn label_message(&self) -> String {
+        self.label.clone()
+    }
+
+    fn help(&self) -> Option<String> {
+        self.help.clone()
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl ParamsInvalid {
+    #[must_use]
+    pub fn new(
+        span: Range<usize>,
+        problem: String,
+        label: String,
+        help: Option<String>,
+        processed: &Processed,
+    ) -> Self {
+        Self {
+            span,
+            problem,
+            label,
+            help,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,56 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct ParamsUnused {
+    span: Range<usize>,
+    variable: String,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for ParamsUnused {
+    fn ident(&self) -> &'static str {
+        "SAW4"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!("`{}` is defined in `params` but never used", self.variable)
+    }
+
+    fn label_message(&self) -> String {
+        String::from("never used")
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "use an empty string `\"\"` to skip an argument without defining a variable",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl ParamsUnused {
+    #[must_use]
+    pub fn new(span: Range<usize>, variable: String, processed: &Processed) -> Self {
+        Self {
+            span,
+            variable,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
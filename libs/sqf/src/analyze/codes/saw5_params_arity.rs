@@ -0,0 +1,98 @@
+use std::ops::Range;
+
+use hemtt_common::{
+    position::Position,
+    reporting::{Code, Diagnostic, Label, Processed, Severity},
+};
+
+pub struct ParamsArity {
+    span: Range<usize>,
+    function: String,
+    given: usize,
+    expected: (usize, usize),
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for ParamsArity {
+    fn ident(&self) -> &'static str {
+        "SAW5"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`{}` is called with {} argument{}",
+            self.function,
+            self.given,
+            if self.given == 1 { "" } else { "s" }
+        )
+    }
+
+    fn label_message(&self) -> String {
+        let (required, total) = self.expected;
+        if required == total {
+            format!("expects {total}")
+        } else {
+            format!("expects {required} to {total}")
+        }
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl ParamsArity {
+    #[must_use]
+    pub fn new(
+        span: Range<usize>,
+        function: String,
+        given: usize,
+        expected: (usize, usize),
+        processed: &Processed,
+    ) -> Self {
+        Self {
+            span,
+            function,
+            given,
+            expected,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    #[must_use]
+    /// A call of a function from another file, at a position in the original source
+    pub fn at(
+        position: &Position,
+        function: String,
+        given: usize,
+        expected: (usize, usize),
+    ) -> Self {
+        let mut code = Self {
+            span: position.span(),
+            function,
+            given,
+            expected,
+
+            diagnostic: None,
+        };
+        code.diagnostic = Some(
+            Diagnostic::simple(&code).with_label(
+                Label::primary(position.path().clone(), position.span())
+                    .with_message(code.label_message()),
+            ),
+        );
+        code
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,66 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct FormatMissingArgument {
+    span: Range<usize>,
+    command: String,
+    placeholder: usize,
+    arguments: usize,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for FormatMissingArgument {
+    fn ident(&self) -> &'static str {
+        "SAW6"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`{}` uses `%{}`, but is given {} argument{}",
+            self.command,
+            self.placeholder,
+            self.arguments,
+            if self.arguments == 1 { "" } else { "s" }
+        )
+    }
+
+    fn label_message(&self) -> String {
+        format!("`%{}` has no argument", self.placeholder)
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl FormatMissingArgument {
+    #[must_use]
+    pub fn new(
+        span: Range<usize>,
+        command: String,
+        placeholder: usize,
+        arguments: usize,
+        processed: &Processed,
+    ) -> Self {
+        Self {
+            span,
+            command,
+            placeholder,
+            arguments,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,67 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct FormatUnusedArgument {
+    span: Range<usize>,
+    command: String,
+    argument: usize,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for FormatUnusedArgument {
+    fn ident(&self) -> &'static str {
+        "SAW7"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "argument {} of `{}` is never used",
+            self.argument, self.command
+        )
+    }
+
+    fn label_message(&self) -> String {
+        format!("`%{}` is not in the format string", self.argument)
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "add `%{}` to the format string, or remove the argument",
+            self.argument
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl FormatUnusedArgument {
+    #[must_use]
+    pub fn new(
+        span: Range<usize>,
+        command: String,
+        argument: usize,
+        processed: &Processed,
+    ) -> Self {
+        Self {
+            span,
+            command,
+            argument,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,56 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct FormatInvalidPlaceholder {
+    span: Range<usize>,
+    placeholder: String,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for FormatInvalidPlaceholder {
+    fn ident(&self) -> &'static str {
+        "SAW8"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!("`{}` is not a valid placeholder", self.placeholder)
+    }
+
+    fn label_message(&self) -> String {
+        format!("contains `{}`", self.placeholder)
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "placeholders are `%1`, `%2`, ... for the first, second, ... argument",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl FormatInvalidPlaceholder {
+    #[must_use]
+    pub fn new(span: Range<usize>, placeholder: String, processed: &Processed) -> Self {
+        Self {
+            span,
+            placeholder,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,56 @@
+use std::ops::Range;
+
+use hemtt_common::reporting::{Code, Diagnostic, Processed, Severity};
+
+pub struct RemoteExecDynamic {
+    span: Range<usize>,
+    command: String,
+
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for RemoteExecDynamic {
+    fn ident(&self) -> &'static str {
+        "SAW9"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!("`{}` target can not be whitelisted", self.command)
+    }
+
+    fn label_message(&self) -> String {
+        String::from("computed at runtime")
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(String::from(
+            "only targets written as a string can be added to the suggested `CfgRemoteExec`",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl RemoteExecDynamic {
+    #[must_use]
+    pub fn new(span: Range<usize>, command: String, processed: &Processed) -> Self {
+        Self {
+            span,
+            command,
+
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        self.diagnostic = Diagnostic::new_for_processed(&self, self.span.clone(), processed);
+        self
+    }
+}
@@ -0,0 +1,80 @@
+use std::{collections::HashMap, sync::Arc};
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{
+    analyze::codes::saa10_count_empty::CountEmpty, BinaryCommand, Expression, Statement,
+    Statements, UnaryCommand,
+};
+
+pub fn count_empty(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let arrays = arrays(statements);
+    let mut advice: Vec<Arc<dyn Code>> = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            advice.extend(check_expression(expression, &arrays, processed));
+        }
+    }
+    advice
+}
+
+/// Variables that are only ever assigned an array literal in the file, by lowercase name
+fn arrays(statements: &Statements) -> HashMap<String, bool> {
+    let mut arrays = HashMap::new();
+    for statement in statements.content() {
+        for statement in statement.walk_statements() {
+            if let Statement::AssignGlobal(name, value, _)
+            | Statement::AssignLocal(name, value, _) = statement
+            {
+                let is_array = matches!(value, Expression::Array(..));
+                arrays
+                    .entry(name.to_lowercase())
+                    .and_modify(|known| *known &= is_array)
+                    .or_insert(is_array);
+            }
+        }
+    }
+    arrays
+}
+
+fn check_expression(
+    expression: &Expression,
+    arrays: &HashMap<String, bool>,
+    processed: &Processed,
+) -> Vec<Arc<dyn Code>> {
+    let Expression::BinaryCommand(BinaryCommand::Eq, lhs, rhs, _) = expression else {
+        return Vec::new();
+    };
+    let count = match (&**lhs, &**rhs) {
+        (count, Expression::Number(zero, _)) | (Expression::Number(zero, _), count)
+            if zero.0 == 0.0 =>
+        {
+            count
+        }
+        _ => return Vec::new(),
+    };
+    let Expression::UnaryCommand(UnaryCommand::Named(name), array, _) = count else {
+        return Vec::new();
+    };
+    if !name.eq_ignore_ascii_case("count") {
+        return Vec::new();
+    }
+    // strings can also be counted, so only arrays and variables that always hold one
+    // can be compared to `[]`
+    let is_array = match &**array {
+        Expression::Array(..) => true,
+        Expression::Variable(name, _) => arrays
+            .get(&name.to_lowercase())
+            .copied()
+            .unwrap_or_default(),
+        _ => false,
+    };
+    if !is_array {
+        return Vec::new();
+    }
+    vec![Arc::new(CountEmpty::new(
+        expression.full_span(),
+        array.source(),
+        processed,
+    ))]
+}
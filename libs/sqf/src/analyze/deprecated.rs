@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use hemtt_common::{
+    project::ProjectConfig,
+    reporting::{Code, Processed},
+};
+
+use crate::{
+    analyze::codes::saw1_deprecated::Deprecated, parser::database::Database, BinaryCommand,
+    Expression, Statements, UnaryCommand,
+};
+
+pub fn deprecated(
+    statements: &Statements,
+    project: Option<&ProjectConfig>,
+    processed: &Processed,
+    database: &Database,
+) -> Vec<Arc<dyn Code>> {
+    let options = project.map(|p| p.lints().sqf().deprecated());
+    if options.is_some_and(|o| !o.enabled()) {
+        return Vec::new();
+    }
+    let mut warnings: Vec<Arc<dyn Code>> = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            let Some((name, span)) = command_name(expression) else {
+                continue;
+            };
+            let Some(deprecation) = database.deprecation(name) else {
+                continue;
+            };
+            if options.is_some_and(|o| o.is_ignored(name)) {
+                continue;
+            }
+            warnings.push(Arc::new(Deprecated::new(
+                span,
+                name.to_string(),
+                deprecation
+                    .replacement()
+                    .map(std::string::ToString::to_string),
+                deprecation.reason().map(std::string::ToString::to_string),
+                processed,
+            )));
+        }
+    }
+    warnings
+}
+
+/// The name of a command, or a variable that may hold a function
+fn command_name(expression: &Expression) -> Option<(&str, std::ops::Range<usize>)> {
+    match expression {
+        Expression::NularCommand(command, span) => Some((command.as_str(), span.clone())),
+        Expression::UnaryCommand(UnaryCommand::Named(name), _, span)
+        | Expression::BinaryCommand(BinaryCommand::Named(name), _, _, span)
+        | Expression::Variable(name, span) => Some((name.as_str(), span.clone())),
+        _ => None,
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{
+    analyze::codes::saa7_for_count::ForCount, BinaryCommand, Expression, Statements, UnaryCommand,
+};
+
+pub fn for_count(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let mut advice: Vec<Arc<dyn Code>> = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            advice.extend(check_expression(expression, processed));
+        }
+    }
+    advice
+}
+
+/// Finds `for "_i" from 0 to count _array - 1 do { ... _array select _i ... }`
+fn check_expression(expression: &Expression, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let Expression::BinaryCommand(BinaryCommand::Named(name), range, body, _) = expression else {
+        return Vec::new();
+    };
+    if !name.eq_ignore_ascii_case("do") {
+        return Vec::new();
+    }
+    let Expression::Code(body) = &**body else {
+        return Vec::new();
+    };
+    let Expression::BinaryCommand(BinaryCommand::Named(to), from, end, _) = &**range else {
+        return Vec::new();
+    };
+    let Expression::BinaryCommand(BinaryCommand::Named(from_name), for_cmd, start, _) = &**from
+    else {
+        return Vec::new();
+    };
+    if !to.eq_ignore_ascii_case("to") || !from_name.eq_ignore_ascii_case("from") {
+        return Vec::new();
+    }
+    let Expression::UnaryCommand(UnaryCommand::Named(for_name), variable, _) = &**for_cmd else {
+        return Vec::new();
+    };
+    let Expression::String(variable, _) = &**variable else {
+        return Vec::new();
+    };
+    if !for_name.eq_ignore_ascii_case("for")
+        || !matches!(&**start, Expression::Number(n, _) if n.0 == 0.0)
+    {
+        return Vec::new();
+    }
+    let Expression::BinaryCommand(BinaryCommand::Sub, count, one, _) = &**end else {
+        return Vec::new();
+    };
+    if !matches!(&**one, Expression::Number(n, _) if (n.0 - 1.0).abs() < f32::EPSILON) {
+        return Vec::new();
+    }
+    let Expression::UnaryCommand(UnaryCommand::Named(count_name), array, _) = &**count else {
+        return Vec::new();
+    };
+    let Expression::Variable(array, _) = &**array else {
+        return Vec::new();
+    };
+    if !count_name.eq_ignore_ascii_case("count") {
+        return Vec::new();
+    }
+    let indexed = body.content().iter().any(|statement| {
+        statement.walk_expressions().into_iter().any(|expression| {
+            let Expression::BinaryCommand(command, target, index, _) = expression else {
+                return false;
+            };
+            let select = match command {
+                BinaryCommand::Select => true,
+                BinaryCommand::Named(name) => name.eq_ignore_ascii_case("select"),
+                _ => false,
+            };
+            select
+                && matches!(&**target, Expression::Variable(name, _) if name.eq_ignore_ascii_case(array))
+                && matches!(&**index, Expression::Variable(name, _) if name.eq_ignore_ascii_case(variable))
+        })
+    });
+    if !indexed {
+        return Vec::new();
+    }
+    vec![Arc::new(ForCount::new(
+        for_cmd.span().start..end.full_span().end,
+        array.clone(),
+        processed,
+    ))]
+}
@@ -0,0 +1,308 @@
+//! Checks the placeholders of `format` and `formatText` against their arguments
+
+use std::{cell::OnceCell, sync::Arc};
+
+use hemtt_common::{
+    addons::Addon,
+    reporting::{Code, Processed},
+};
+
+use crate::{BinaryCommand, Expression, NularCommand, Statements, UnaryCommand};
+
+use super::codes::{
+    saa4_str_format::StrFormat, saw6_format_missing_argument::FormatMissingArgument,
+    saw7_format_unused_argument::FormatUnusedArgument,
+    saw8_format_invalid_placeholder::FormatInvalidPlaceholder,
+};
+
+/// Commands that take `[format, arg1, arg2, ...]`
+const FORMAT_COMMANDS: &[&str] = &["format", "formattext"];
+
+/// Commands that never return a string, which `str` formats the same as `format ["%1", ...]`
+const NON_STRING_COMMANDS: &[&str] = &[
+    "abs",
+    "alive",
+    "allPlayers",
+    "allUnits",
+    "ceil",
+    "count",
+    "damage",
+    "diag_tickTime",
+    "direction",
+    "distance",
+    "distance2D",
+    "floor",
+    "getDir",
+    "getPos",
+    "getPosASL",
+    "getPosATL",
+    "getPosWorld",
+    "group",
+    "isNil",
+    "isNull",
+    "player",
+    "position",
+    "random",
+    "round",
+    "serverTime",
+    "side",
+    "speed",
+    "sqrt",
+    "time",
+    "units",
+    "vehicle",
+    "velocity",
+];
+
+/// Letters that are commonly used as placeholders in other languages, `%s`, `%d`, ...
+const FOREIGN_PLACEHOLDERS: &[char] = &['s', 'd', 'i', 'f'];
+
+pub fn format(
+    statements: &Statements,
+    processed: &Processed,
+    addon: Option<&Addon>,
+) -> Vec<Arc<dyn Code>> {
+    let mut warnings: Vec<Arc<dyn Code>> = Vec::new();
+    // only read when a format uses `localize`
+    let stringtable = OnceCell::new();
+    let context = Context {
+        processed,
+        addon,
+        stringtable: &stringtable,
+    };
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            check_expression(expression, &context, &mut warnings);
+        }
+    }
+    warnings
+}
+
+struct Context<'a> {
+    processed: &'a Processed,
+    addon: Option<&'a Addon>,
+    stringtable: &'a OnceCell<Stringtable>,
+}
+
+impl Context<'_> {
+    fn stringtable(&self) -> &Stringtable {
+        self.stringtable
+            .get_or_init(|| Stringtable::load(self.processed, self.addon))
+    }
+}
+
+fn check_expression(expression: &Expression, context: &Context, warnings: &mut Vec<Arc<dyn Code>>) {
+    let processed = context.processed;
+    let Expression::UnaryCommand(UnaryCommand::Named(command), target, _) = expression else {
+        return;
+    };
+    if !FORMAT_COMMANDS.contains(&command.to_ascii_lowercase().as_str()) {
+        return;
+    }
+    let Expression::Array(elements, _) = &**target else {
+        return;
+    };
+    let Some((first, args)) = elements.split_first() else {
+        return;
+    };
+    let (format, span) = match first {
+        Expression::String(format, span) => (format.clone(), span.clone()),
+        // `localize "STR_..."` is checked with the text from the addon's stringtable
+        Expression::UnaryCommand(UnaryCommand::Named(localize), key, _)
+            if localize.eq_ignore_ascii_case("localize") =>
+        {
+            let Expression::String(key, _) = &**key else {
+                return;
+            };
+            let Some(format) = context.stringtable().get(key) else {
+                return;
+            };
+            (format.to_string(), first.full_span())
+        }
+        // the format is built at runtime
+        _ => return,
+    };
+    let placeholders = Placeholders::parse(&format);
+    // an invalid placeholder was likely meant for one of the arguments
+    let check_unused = placeholders.invalid.is_empty();
+    for invalid in placeholders.invalid {
+        warnings.push(Arc::new(FormatInvalidPlaceholder::new(
+            span.clone(),
+            invalid,
+            processed,
+        )));
+    }
+    if let Some(highest) = placeholders.used.iter().max() {
+        if *highest > args.len() {
+            warnings.push(Arc::new(FormatMissingArgument::new(
+                span,
+                command.clone(),
+                *highest,
+                args.len(),
+                processed,
+            )));
+        }
+    }
+    for (index, arg) in args.iter().enumerate() {
+        if check_unused && !placeholders.used.contains(&(index + 1)) {
+            warnings.push(Arc::new(FormatUnusedArgument::new(
+                arg.full_span(),
+                command.clone(),
+                index + 1,
+                processed,
+            )));
+        }
+    }
+    // `str` quotes strings, so it is only equivalent for values that are known not to be strings
+    if command.eq_ignore_ascii_case("format")
+        && matches!(first, Expression::String(_, _))
+        && format == "%1"
+        && args.len() == 1
+        && is_not_string(&args[0])
+    {
+        if let Some(code) = StrFormat::new(expression.full_span(), args[0].clone(), processed) {
+            warnings.push(Arc::new(code));
+        }
+    }
+}
+
+/// Is an expression known to never result in a string
+fn is_not_string(expression: &Expression) -> bool {
+    match expression {
+        Expression::NularCommand(NularCommand { name: command }, _)
+        | Expression::UnaryCommand(UnaryCommand::Named(command), _, _)
+        | Expression::BinaryCommand(BinaryCommand::Named(command), _, _, _) => {
+            is_in(NON_STRING_COMMANDS, command)
+        }
+        // `+` also joins strings, and `select` and `else` return any value
+        Expression::BinaryCommand(
+            BinaryCommand::Add | BinaryCommand::Select | BinaryCommand::Else,
+            _,
+            _,
+            _,
+        ) => false,
+        // literals, negation, comparisons and arithmetic
+        Expression::Number(_, _)
+        | Expression::Boolean(_, _)
+        | Expression::Array(_, _)
+        | Expression::UnaryCommand(UnaryCommand::Minus | UnaryCommand::Not, _, _)
+        | Expression::BinaryCommand(_, _, _, _) => true,
+        _ => false,
+    }
+}
+
+fn is_in(list: &[&str], command: &str) -> bool {
+    list.iter().any(|c| c.eq_ignore_ascii_case(command))
+}
+
+/// The text of each key of an addon's `stringtable.xml`
+#[derive(Default)]
+struct Stringtable {
+    /// Lowercase key and the original or English text
+    keys: Vec<(String, String)>,
+}
+
+impl Stringtable {
+    fn load(processed: &Processed, addon: Option<&Addon>) -> Self {
+        let (Some(addon), Some((path, _))) = (addon, processed.source(0)) else {
+            return Self::default();
+        };
+        let Ok(Some(path)) = path.locate(&format!("/{}/stringtable.xml", addon.folder())) else {
+            return Self::default();
+        };
+        path.read_to_string()
+            .map_or_else(|_| Self::default(), |xml| Self::parse(&xml))
+    }
+
+    fn parse(xml: &str) -> Self {
+        let mut keys = Vec::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find("<Key ID=\"") {
+            rest = &rest[start + 9..];
+            let Some(id_end) = rest.find('"') else {
+                break;
+            };
+            let id = rest[..id_end].to_ascii_lowercase();
+            let body = rest.find("</Key>").map_or(rest, |end| &rest[..end]);
+            if let Some(text) = ["Original", "English"]
+                .iter()
+                .find_map(|language| element(body, language))
+            {
+                keys.push((id, unescape(text)));
+            }
+            rest = &rest[body.len()..];
+        }
+        Self { keys }
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        let key = key.to_ascii_lowercase();
+        self.keys
+            .iter()
+            .find(|(id, _)| *id == key)
+            .map(|(_, text)| text.as_str())
+    }
+}
+
+/// The text of an element, such as `<English>text</English>`
+fn element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{name}>"))? + name.len() + 2;
+    let end = xml[start..].find(&format!("</{name}>"))?;
+    Some(&xml[start..start + end])
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The placeholders found in a format string
+struct Placeholders {
+    /// Arguments that are used, starting at 1
+    used: Vec<usize>,
+    /// Placeholders that will not be replaced
+    invalid: Vec<String>,
+}
+
+impl Placeholders {
+    fn parse(format: &str) -> Self {
+        let mut used = Vec::new();
+        let mut invalid = Vec::new();
+        let mut chars = format.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                continue;
+            }
+            let mut digits = String::new();
+            while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                digits.push(digit);
+            }
+            if digits.is_empty() {
+                let Some(letter) = chars.next_if(|c| FOREIGN_PLACEHOLDERS.contains(c)) else {
+                    // `%%` is a literal percent sign
+                    chars.next_if_eq(&'%');
+                    continue;
+                };
+                // `50%dps` is more likely to be text than a placeholder
+                if chars.peek().is_some_and(char::is_ascii_alphanumeric) {
+                    continue;
+                }
+                invalid.push(format!("%{letter}"));
+                continue;
+            }
+            match digits.parse::<usize>() {
+                Ok(0) | Err(_) => {
+                    let placeholder = format!("%{digits}");
+                    if !invalid.contains(&placeholder) {
+                        invalid.push(placeholder);
+                    }
+                }
+                Ok(index) => used.push(index),
+            }
+        }
+        Self { used, invalid }
+    }
+}
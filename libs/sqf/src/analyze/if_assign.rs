@@ -7,6 +7,8 @@ use crate::{
     UnaryCommand,
 };
 
+use super::if_bool::if_bool_parts;
+
 pub fn if_assign(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
     let mut advice: Vec<Arc<dyn Code>> = Vec::new();
     for statement in statements.content() {
@@ -18,6 +20,10 @@ pub fn if_assign(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn
 }
 
 fn check_expression(expression: &Expression, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    // reported by SAA8 instead
+    if if_bool_parts(expression).is_some() {
+        return Vec::new();
+    }
     if let Expression::BinaryCommand(BinaryCommand::Named(name), if_cmd, code, _) = expression {
         if name == "then" {
             let Expression::UnaryCommand(UnaryCommand::Named(_), condition, _) = &**if_cmd else {
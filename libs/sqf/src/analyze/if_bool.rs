@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{
+    analyze::codes::saa8_if_bool::IfBool, BinaryCommand, Expression, Statement, Statements,
+    UnaryCommand,
+};
+
+pub fn if_bool(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let mut advice: Vec<Arc<dyn Code>> = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            advice.extend(check_expression(expression, processed));
+        }
+    }
+    advice
+}
+
+fn check_expression(expression: &Expression, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let Some((if_cmd, condition, negated)) = if_bool_parts(expression) else {
+        return Vec::new();
+    };
+    // the span of code ends at its last statement, so include the closing brace
+    let end = expression.full_span().end;
+    let end = end + processed.as_str()[end..].find('}').map_or(0, |i| i + 1);
+    vec![Arc::new(IfBool::new(
+        if_cmd.span().start..end,
+        condition.source(),
+        negated,
+        processed,
+    ))]
+}
+
+/// The `if`, its condition, and whether it returns the negated condition,
+/// for `if (condition) then {true} else {false}` and the reverse
+pub fn if_bool_parts(expression: &Expression) -> Option<(&Expression, &Expression, bool)> {
+    let Expression::BinaryCommand(BinaryCommand::Named(name), if_cmd, code, _) = expression else {
+        return None;
+    };
+    if !name.eq_ignore_ascii_case("then") {
+        return None;
+    }
+    let Expression::UnaryCommand(UnaryCommand::Named(_), condition, _) = &**if_cmd else {
+        return None;
+    };
+    let Expression::BinaryCommand(BinaryCommand::Else, lhs, rhs, _) = &**code else {
+        return None;
+    };
+    match (constant_bool(lhs)?, constant_bool(rhs)?) {
+        (true, false) => Some((if_cmd, condition, false)),
+        (false, true) => Some((if_cmd, condition, true)),
+        _ => None,
+    }
+}
+
+fn constant_bool(expression: &Expression) -> Option<bool> {
+    let Expression::Code(code) = expression else {
+        return None;
+    };
+    match code.content() {
+        [Statement::Expression(Expression::Boolean(value, _), _)] => Some(*value),
+        _ => None,
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{analyze::codes::saa9_is_nil_local::IsNilLocal, Expression, Statements, UnaryCommand};
+
+pub fn is_nil_local(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let mut advice: Vec<Arc<dyn Code>> = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            advice.extend(check_expression(expression, processed));
+        }
+    }
+    advice
+}
+
+fn check_expression(expression: &Expression, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let Expression::UnaryCommand(UnaryCommand::Named(name), variable, _) = expression else {
+        return Vec::new();
+    };
+    if !name.eq_ignore_ascii_case("isnil") {
+        return Vec::new();
+    }
+    let Expression::String(variable, _) = &**variable else {
+        return Vec::new();
+    };
+    if !variable.starts_with('_')
+        || !variable
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Vec::new();
+    }
+    vec![Arc::new(IsNilLocal::new(
+        expression.full_span(),
+        variable.clone(),
+        processed,
+    ))]
+}
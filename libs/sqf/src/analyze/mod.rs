@@ -1,11 +1,23 @@
 pub mod codes;
 
+mod argument_type;
+mod call_compile;
+mod count_empty;
+mod deprecated;
 mod find_in_str;
+mod for_count;
+mod format;
 mod if_assign;
+mod if_bool;
+mod is_nil_local;
+mod null_compare;
+mod params;
+pub mod remote_exec;
 mod required_version;
+mod scheduled;
 mod select_parse_number;
-mod str_format;
 mod typename;
+mod unreachable;
 
 use std::sync::Arc;
 
@@ -17,6 +29,8 @@ use hemtt_common::{
 
 use crate::{parser::database::Database, Statements};
 
+pub use self::params::{arity, global_calls};
+
 type Codes = Vec<Arc<dyn Code>>;
 
 pub trait Analyze {
@@ -43,10 +57,43 @@ pub trait Analyze {
 #[must_use]
 pub fn analyze(
     statements: &Statements,
-    _project: Option<&ProjectConfig>,
+    project: Option<&ProjectConfig>,
+    processed: &Processed,
+    addon: Option<&Addon>,
+    database: &Database,
+) -> (Codes, Codes) {
+    run(statements, project, processed, addon, database, None)
+}
+
+#[must_use]
+/// Analyze code that is known to run unscheduled, such as CBA extended event handlers in config
+///
+/// `reason` explains why the code is unscheduled, when a suspending command is reported
+pub fn analyze_unscheduled(
+    statements: &Statements,
+    project: Option<&ProjectConfig>,
     processed: &Processed,
     addon: Option<&Addon>,
     database: &Database,
+    reason: &str,
+) -> (Codes, Codes) {
+    run(
+        statements,
+        project,
+        processed,
+        addon,
+        database,
+        Some(reason),
+    )
+}
+
+fn run(
+    statements: &Statements,
+    project: Option<&ProjectConfig>,
+    processed: &Processed,
+    addon: Option<&Addon>,
+    database: &Database,
+    unscheduled: Option<&str>,
 ) -> (Codes, Codes) {
     (
         {
@@ -54,11 +101,27 @@ pub fn analyze(
             warnings.extend(if_assign::if_assign(statements, processed));
             warnings.extend(find_in_str::find_in_str(statements, processed));
             warnings.extend(typename::typename(statements, processed));
-            // warnings.extend(str_format::str_format(statements, processed)); // Too many false positives for now
+            warnings.extend(format::format(statements, processed, addon));
             warnings.extend(select_parse_number::select_parse_number(
                 statements, processed,
             ));
-            warnings
+            warnings.extend(deprecated::deprecated(
+                statements, project, processed, database,
+            ));
+            warnings.extend(scheduled::scheduled(statements, processed, unscheduled));
+            warnings.extend(params::params(statements, processed));
+            warnings.extend(remote_exec::remote_exec(statements, processed));
+            warnings.extend(call_compile::call_compile(statements, processed));
+            warnings.extend(for_count::for_count(statements, processed));
+            warnings.extend(if_bool::if_bool(statements, processed));
+            warnings.extend(is_nil_local::is_nil_local(statements, processed));
+            warnings.extend(count_empty::count_empty(statements, processed));
+            warnings.extend(unreachable::unreachable(statements, processed));
+            warnings.extend(null_compare::null_compare(statements, processed));
+            warnings.extend(argument_type::argument_type(
+                statements, processed, database,
+            ));
+            processed.unsuppressed(warnings)
         },
         {
             let mut errors = Vec::new();
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{
+    analyze::codes::saw11_null_compare::NullCompare, BinaryCommand, Expression, Statements,
+};
+
+/// Null values that are never equal to anything, including themselves
+const NULLS: &[&str] = &[
+    "objnull",
+    "grpnull",
+    "controlnull",
+    "displaynull",
+    "locationnull",
+    "scriptnull",
+    "tasknull",
+    "teammembernull",
+];
+
+pub fn null_compare(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let mut warnings: Vec<Arc<dyn Code>> = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            warnings.extend(check_expression(expression, processed));
+        }
+    }
+    warnings
+}
+
+fn check_expression(expression: &Expression, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let (negated, lhs, rhs) = match expression {
+        Expression::BinaryCommand(BinaryCommand::Eq, lhs, rhs, _) => (false, lhs, rhs),
+        Expression::BinaryCommand(BinaryCommand::NotEq, lhs, rhs, _) => (true, lhs, rhs),
+        _ => return Vec::new(),
+    };
+    let (null, value) = match (&**lhs, &**rhs) {
+        (Expression::NularCommand(null, _), value) | (value, Expression::NularCommand(null, _))
+            if NULLS.contains(&null.as_str().to_lowercase().as_str()) =>
+        {
+            (null, value)
+        }
+        _ => return Vec::new(),
+    };
+    vec![Arc::new(NullCompare::new(
+        expression.full_span(),
+        null.as_str().to_string(),
+        value.source(),
+        negated,
+        processed,
+    ))]
+}
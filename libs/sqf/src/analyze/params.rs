@@ -0,0 +1,453 @@
+//! Validates `params` definitions, their usage, and the arguments passed to functions in the same file
+//!
+//! [`arity`] and [`global_calls`] let the arguments passed to functions in other files be checked
+
+use std::{collections::HashMap, ops::Range, sync::Arc};
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{BinaryCommand, Expression, Statement, Statements, UnaryCommand};
+
+use super::codes::{
+    saw3_params_invalid::ParamsInvalid, saw4_params_unused::ParamsUnused,
+    saw5_params_arity::ParamsArity,
+};
+
+/// Type names returned by `typeName`, and an exemplar of each
+const TYPE_EXEMPLARS: &[(&str, &str)] = &[
+    ("ARRAY", "[]"),
+    ("BOOL", "false"),
+    ("CODE", "{}"),
+    ("CONFIG", "configNull"),
+    ("CONTROL", "controlNull"),
+    ("DIARY_RECORD", "diaryRecordNull"),
+    ("DISPLAY", "displayNull"),
+    ("GROUP", "grpNull"),
+    ("HASHMAP", "createHashMap"),
+    ("LOCATION", "locationNull"),
+    ("NAMESPACE", "missionNamespace"),
+    ("OBJECT", "objNull"),
+    ("SCALAR", "0"),
+    ("SCRIPT", "scriptNull"),
+    ("SIDE", "sideUnknown"),
+    ("STRING", "\"\""),
+    ("TASK", "taskNull"),
+    ("TEAM_MEMBER", "teamMemberNull"),
+    ("TEXT", "text \"\""),
+];
+
+pub fn params(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let mut warnings: Vec<Arc<dyn Code>> = Vec::new();
+    check_block(statements, processed, &mut warnings);
+    check_calls(statements, processed, &mut warnings);
+    warnings
+}
+
+/// A variable defined by `params`
+struct Param {
+    name: String,
+    span: Range<usize>,
+}
+
+/// Check the `params` in a block of code, then any nested blocks
+fn check_block(statements: &Statements, processed: &Processed, warnings: &mut Vec<Arc<dyn Code>>) {
+    let mut params = Vec::new();
+    let mut nested = Vec::new();
+    for statement in statements.content() {
+        let mut expressions = Vec::new();
+        match statement {
+            Statement::AssignGlobal(_, expression, _)
+            | Statement::AssignLocal(_, expression, _)
+            | Statement::Expression(expression, _) => {
+                shallow_expressions(expression, &mut expressions, &mut nested);
+            }
+        }
+        for expression in expressions {
+            if let Some(elements) = params_elements(expression) {
+                params.extend(check_params(elements, processed, warnings));
+            }
+        }
+    }
+    if !params.is_empty() {
+        let used = used_names(statements);
+        for param in params {
+            if !used.contains(&param.name.to_ascii_lowercase()) {
+                warnings.push(Arc::new(ParamsUnused::new(
+                    param.span, param.name, processed,
+                )));
+            }
+        }
+    }
+    for code in nested {
+        check_block(code, processed, warnings);
+    }
+}
+
+/// The expressions of a statement, without descending into code blocks
+fn shallow_expressions<'a>(
+    expression: &'a Expression,
+    expressions: &mut Vec<&'a Expression>,
+    nested: &mut Vec<&'a Statements>,
+) {
+    expressions.push(expression);
+    match expression {
+        Expression::Code(code) => nested.push(code),
+        Expression::UnaryCommand(_, child, _) => shallow_expressions(child, expressions, nested),
+        Expression::BinaryCommand(_, left, right, _) => {
+            shallow_expressions(left, expressions, nested);
+            shallow_expressions(right, expressions, nested);
+        }
+        Expression::Array(elements, _) => {
+            for element in elements {
+                shallow_expressions(element, expressions, nested);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The elements of a `params [...]` or `_var params [...]` definition
+fn params_elements(expression: &Expression) -> Option<&[Expression]> {
+    let definition = match expression {
+        Expression::UnaryCommand(UnaryCommand::Named(name), definition, _)
+        | Expression::BinaryCommand(BinaryCommand::Named(name), _, definition, _)
+            if name.eq_ignore_ascii_case("params") =>
+        {
+            definition
+        }
+        _ => return None,
+    };
+    let Expression::Array(elements, _) = &**definition else {
+        return None;
+    };
+    Some(elements)
+}
+
+/// Validate each element of a `params` definition, returning the variables it defines
+fn check_params(
+    elements: &[Expression],
+    processed: &Processed,
+    warnings: &mut Vec<Arc<dyn Code>>,
+) -> Vec<Param> {
+    let mut params = Vec::new();
+    for element in elements {
+        let name = match element {
+            Expression::Array(items, _) => {
+                let Some(name) = items.first() else {
+                    warnings.push(Arc::new(ParamsInvalid::new(
+                        element.span(),
+                        String::from("empty parameter"),
+                        String::from("expected a variable name"),
+                        None,
+                        processed,
+                    )));
+                    continue;
+                };
+                check_types(items, processed, warnings);
+                name
+            }
+            _ => element,
+        };
+        match name {
+            Expression::String(name, span) => {
+                if name.is_empty() {
+                    continue;
+                }
+                if !name.starts_with('_') {
+                    warnings.push(Arc::new(ParamsInvalid::new(
+                        span.clone(),
+                        format!("`{name}` is not a local variable"),
+                        String::from("must start with `_`"),
+                        Some(format!("use `_{name}`")),
+                        processed,
+                    )));
+                    continue;
+                }
+                params.push(Param {
+                    name: name.clone(),
+                    span: span.clone(),
+                });
+            }
+            Expression::Number(_, span) | Expression::Boolean(_, span) => {
+                warnings.push(Arc::new(ParamsInvalid::new(
+                    span.clone(),
+                    String::from("expected a variable name"),
+                    String::from("not a string"),
+                    None,
+                    processed,
+                )));
+            }
+            _ => {}
+        }
+    }
+    params
+}
+
+/// Validate the expected types of a parameter, and that the default matches them
+fn check_types(items: &[Expression], processed: &Processed, warnings: &mut Vec<Arc<dyn Code>>) {
+    let Some(types) = items.get(2) else {
+        return;
+    };
+    let exemplars = match types {
+        Expression::Array(exemplars, _) => exemplars,
+        Expression::String(_, span)
+        | Expression::Number(_, span)
+        | Expression::Boolean(_, span) => {
+            warnings.push(Arc::new(ParamsInvalid::new(
+                span.clone(),
+                String::from("expected types must be an array"),
+                String::from("not an array"),
+                Some(format!("use `[{}]`", types.source())),
+                processed,
+            )));
+            return;
+        }
+        _ => return,
+    };
+    let mut valid = true;
+    for exemplar in exemplars {
+        if is_nil(exemplar) {
+            valid = false;
+            warnings.push(Arc::new(ParamsInvalid::new(
+                exemplar.span(),
+                String::from("`nil` is not a type"),
+                String::from("not a type exemplar"),
+                Some(String::from(
+                    "allow any type by omitting the expected types",
+                )),
+                processed,
+            )));
+        } else if let Expression::String(name, span) = exemplar {
+            if let Some((_, replacement)) = TYPE_EXEMPLARS.iter().find(|(t, _)| t == name) {
+                valid = false;
+                warnings.push(Arc::new(ParamsInvalid::new(
+                    span.clone(),
+                    format!("`\"{name}\"` is a type name, not a type exemplar"),
+                    String::from("only accepts strings"),
+                    Some(format!("use `{replacement}`")),
+                    processed,
+                )));
+            }
+        }
+    }
+    if !valid || exemplars.is_empty() {
+        return;
+    }
+    let Some(default) = items.get(1) else {
+        return;
+    };
+    let Some(default_type) = value_type(default) else {
+        return;
+    };
+    let mut expected = Vec::new();
+    for exemplar in exemplars {
+        let Some(exemplar_type) = value_type(exemplar) else {
+            return;
+        };
+        expected.push(exemplar_type);
+    }
+    if !expected.contains(&default_type) {
+        warnings.push(Arc::new(ParamsInvalid::new(
+            default.full_span(),
+            format!("default value is {default_type}, which is not an expected type"),
+            format!("expected {}", expected.join(" or ")),
+            None,
+            processed,
+        )));
+    }
+}
+
+/// The type of a literal or constant value, if known
+fn value_type(expression: &Expression) -> Option<&'static str> {
+    match expression {
+        Expression::String(_, _) => Some("STRING"),
+        Expression::Number(_, _) => Some("SCALAR"),
+        Expression::Boolean(_, _) => Some("BOOL"),
+        Expression::Array(_, _) => Some("ARRAY"),
+        Expression::Code(_) => Some("CODE"),
+        Expression::NularCommand(command, _) => {
+            match command.as_str().to_ascii_lowercase().as_str() {
+                "objnull" => Some("OBJECT"),
+                "grpnull" => Some("GROUP"),
+                "controlnull" => Some("CONTROL"),
+                "displaynull" => Some("DISPLAY"),
+                "locationnull" => Some("LOCATION"),
+                "scriptnull" => Some("SCRIPT"),
+                "confignull" | "configfile" => Some("CONFIG"),
+                "tasknull" => Some("TASK"),
+                "teammembernull" => Some("TEAM_MEMBER"),
+                "diaryrecordnull" => Some("DIARY_RECORD"),
+                "createhashmap" => Some("HASHMAP"),
+                "missionnamespace" | "profilenamespace" | "uinamespace" | "parsingnamespace" => {
+                    Some("NAMESPACE")
+                }
+                "sideunknown" | "west" | "east" | "independent" | "resistance" | "civilian"
+                | "sidelogic" | "sideempty" | "sideenemy" | "sidefriendly" => Some("SIDE"),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_nil(expression: &Expression) -> bool {
+    matches!(expression, Expression::NularCommand(command, _) if command.as_str().eq_ignore_ascii_case("nil"))
+}
+
+/// Lowercase names of every variable read in a block, including nested blocks,
+/// and strings that name a variable, such as with `isNil "_var"`
+fn used_names(statements: &Statements) -> Vec<String> {
+    let mut used = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            match expression {
+                Expression::Variable(name, _) => used.push(name.to_ascii_lowercase()),
+                Expression::UnaryCommand(UnaryCommand::Named(command), name, _)
+                    if command.eq_ignore_ascii_case("isNil") =>
+                {
+                    if let Expression::String(name, _) = &**name {
+                        used.push(name.to_ascii_lowercase());
+                    }
+                }
+                Expression::BinaryCommand(BinaryCommand::Named(command), _, name, _)
+                    if command.eq_ignore_ascii_case("getVariable") =>
+                {
+                    // `getVariable "_var"` or `getVariable ["_var", default]`
+                    let name = match &**name {
+                        Expression::Array(elements, _) => elements.first(),
+                        name => Some(name),
+                    };
+                    if let Some(Expression::String(name, _)) = name {
+                        used.push(name.to_ascii_lowercase());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    used
+}
+
+/// Check the number of arguments passed to functions defined in the same file
+fn check_calls(statements: &Statements, processed: &Processed, warnings: &mut Vec<Arc<dyn Code>>) {
+    let mut functions: HashMap<String, Option<(usize, usize)>> = HashMap::new();
+    for statement in statements.content() {
+        for statement in statement.walk_statements() {
+            if let Statement::AssignGlobal(name, Expression::Code(code), _)
+            | Statement::AssignLocal(name, Expression::Code(code), _) = statement
+            {
+                // a function defined more than once can not be checked
+                functions
+                    .entry(name.to_ascii_lowercase())
+                    .and_modify(|arity| *arity = None)
+                    .or_insert_with(|| arity(code));
+            }
+        }
+    }
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            let Expression::BinaryCommand(BinaryCommand::Named(command), args, target, _) =
+                expression
+            else {
+                continue;
+            };
+            if !command.eq_ignore_ascii_case("call") {
+                continue;
+            }
+            let Expression::Variable(function, _) = &**target else {
+                continue;
+            };
+            let Some(Some((required, total))) = functions.get(&function.to_ascii_lowercase())
+            else {
+                continue;
+            };
+            let Some(given) = argument_count(args) else {
+                continue;
+            };
+            // extra arguments are valid, and often used to pass optional context
+            if given < *required {
+                warnings.push(Arc::new(ParamsArity::new(
+                    expression.full_span(),
+                    function.clone(),
+                    given,
+                    (*required, *total),
+                    processed,
+                )));
+            }
+        }
+    }
+}
+
+#[must_use]
+/// Every `call` of a function by a global variable, such as `abe_main_fnc_heal`,
+/// with the number of arguments, when it is known, and the span of the call
+pub fn global_calls(statements: &Statements) -> Vec<(String, usize, Range<usize>)> {
+    let mut calls = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            let Expression::BinaryCommand(BinaryCommand::Named(command), args, target, _) =
+                expression
+            else {
+                continue;
+            };
+            if !command.eq_ignore_ascii_case("call") {
+                continue;
+            }
+            let Expression::Variable(function, _) = &**target else {
+                continue;
+            };
+            if function.starts_with('_') {
+                continue;
+            }
+            if let Some(given) = argument_count(args) {
+                calls.push((function.clone(), given, expression.full_span()));
+            }
+        }
+    }
+    calls
+}
+
+#[must_use]
+/// The number of required and total arguments of a function, from its first `params`
+pub fn arity(code: &Statements) -> Option<(usize, usize)> {
+    let elements = code.content().iter().find_map(|statement| match statement {
+        Statement::Expression(expression, _) => match expression {
+            Expression::UnaryCommand(_, _, _) => params_elements(expression),
+            Expression::BinaryCommand(_, target, _, _)
+                if matches!(&**target, Expression::Variable(name, _) if name == "_this") =>
+            {
+                params_elements(expression)
+            }
+            _ => None,
+        },
+        _ => None,
+    })?;
+    let mut required = 0;
+    for (index, element) in elements.iter().enumerate() {
+        let param = match element {
+            Expression::Array(items, _) => items
+                .first()
+                .map(|name| (name, items.get(1).is_some_and(|d| !is_nil(d)))),
+            _ => Some((element, false)),
+        };
+        match param {
+            Some((Expression::String(name, _), false)) if !name.is_empty() => required = index + 1,
+            Some((Expression::String(_, _), _)) => {}
+            // the definition can not be checked
+            _ => return None,
+        }
+    }
+    Some((required, elements.len()))
+}
+
+/// The number of arguments passed to `call`, if known
+const fn argument_count(args: &Expression) -> Option<usize> {
+    match args {
+        Expression::Array(elements, _) => Some(elements.len()),
+        Expression::String(_, _)
+        | Expression::Number(_, _)
+        | Expression::Boolean(_, _)
+        | Expression::Code(_) => Some(1),
+        _ => None,
+    }
+}
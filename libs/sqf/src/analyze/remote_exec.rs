@@ -0,0 +1,83 @@
+//! Finds the targets of `remoteExec` and `remoteExecCall`, for building a `CfgRemoteExec` whitelist
+
+use std::{ops::Range, sync::Arc};
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{BinaryCommand, Expression, Statements, UnaryCommand};
+
+use super::codes::saw9_remote_exec_dynamic::RemoteExecDynamic;
+
+const REMOTE_EXEC: &[&str] = &["remoteexec", "remoteexeccall"];
+
+/// A use of `remoteExec` or `remoteExecCall`
+pub struct RemoteExec<'a> {
+    /// The command, as written
+    pub command: &'a str,
+    /// The function or command that is executed, if it is a string literal
+    pub target: Option<&'a str>,
+    /// The span of the target, or of the whole arguments if they are not an array
+    pub span: Range<usize>,
+    /// Whether the target is only ever executed on the server, `2`
+    pub server_only: bool,
+}
+
+#[must_use]
+pub fn remote_exec(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    remote_execs(statements)
+        .into_iter()
+        .filter(|remote_exec| remote_exec.target.is_none())
+        .map(|remote_exec| {
+            Arc::new(RemoteExecDynamic::new(
+                remote_exec.span,
+                remote_exec.command.to_string(),
+                processed,
+            )) as Arc<dyn Code>
+        })
+        .collect()
+}
+
+#[must_use]
+/// Every use of `remoteExec` and `remoteExecCall`
+pub fn remote_execs(statements: &Statements) -> Vec<RemoteExec<'_>> {
+    let mut remote_execs = Vec::new();
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            let (command, args) = match expression {
+                Expression::UnaryCommand(UnaryCommand::Named(command), args, _)
+                | Expression::BinaryCommand(BinaryCommand::Named(command), _, args, _)
+                    if REMOTE_EXEC.contains(&command.to_ascii_lowercase().as_str()) =>
+                {
+                    (command, args)
+                }
+                _ => continue,
+            };
+            let Expression::Array(elements, _) = &**args else {
+                remote_execs.push(RemoteExec {
+                    command,
+                    target: None,
+                    span: args.full_span(),
+                    server_only: false,
+                });
+                continue;
+            };
+            let Some(target) = elements.first() else {
+                continue;
+            };
+            remote_execs.push(RemoteExec {
+                command,
+                target: if let Expression::String(name, _) = target {
+                    Some(name)
+                } else {
+                    None
+                },
+                span: target.full_span(),
+                server_only: matches!(
+                    elements.get(1),
+                    Some(Expression::Number(number, _)) if (number.0 - 2.0).abs() < f32::EPSILON
+                ),
+            });
+        }
+    }
+    remote_execs
+}
@@ -0,0 +1,313 @@
+//! Infers which code is provably unscheduled, and reports commands that suspend in it
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    sync::Arc,
+};
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{BinaryCommand, Expression, Statement, Statements, UnaryCommand};
+
+use super::codes::saw2_suspend_unscheduled::SuspendUnscheduled;
+
+/// Commands that suspend the current script
+const SUSPENDING: &[&str] = &["sleep", "uisleep", "waituntil"];
+
+/// Binary commands that add an event handler, `target command [type, code]`
+const EVENT_HANDLERS_BINARY: &[&str] = &[
+    "addeventhandler",
+    "addmpeventhandler",
+    "ctrladdeventhandler",
+    "displayaddeventhandler",
+];
+
+/// Unary commands that add an event handler, `command [type, code]`
+const EVENT_HANDLERS_UNARY: &[&str] = &["addmissioneventhandler"];
+
+/// CBA functions that add an event handler, and the index of the code in their arguments
+const EVENT_HANDLERS_CBA: &[(&str, usize)] = &[
+    ("cba_fnc_addeventhandler", 1),
+    ("cba_fnc_addplayereventhandler", 1),
+    ("cba_fnc_addclasseventhandler", 2),
+];
+
+/// Files called by CBA's extended event handlers from config
+const XEH_FILES: &[&str] = &["xeh_preinit.sqf", "xeh_postinit.sqf", "xeh_prestart.sqf"];
+
+/// Unary commands that execute a code argument immediately, in the same context
+const INLINE_UNARY: &[&str] = &["call", "isnil", "while", "try", "default", "waituntil"];
+
+/// Binary commands that execute code arguments immediately, in the same context
+const INLINE_BINARY: &[&str] = &[
+    "then",
+    "do",
+    "foreach",
+    "foreachreversed",
+    "call",
+    "exitwith",
+    "count",
+    "select",
+    "findif",
+    "apply",
+    "catch",
+];
+
+/// `unscheduled` is the reason the whole of `statements` runs unscheduled, if it is known by the caller
+pub fn scheduled(
+    statements: &Statements,
+    processed: &Processed,
+    unscheduled: Option<&str>,
+) -> Vec<Arc<dyn Code>> {
+    let mut checker = Checker {
+        functions: functions(statements),
+        processed,
+        visited: HashSet::new(),
+        reported: HashSet::new(),
+        warnings: Vec::new(),
+    };
+    let reason = unscheduled.map(str::to_string).or_else(|| {
+        is_xeh_file(statements, processed)
+            .then(|| String::from("CBA extended event handler files run unscheduled"))
+    });
+    if let Some(reason) = reason {
+        checker.check(statements, &Context { span: None, reason });
+    }
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            if let Some((code, context)) = entry_point(expression) {
+                checker.visited.clear();
+                checker.check(code, &context);
+            }
+        }
+    }
+    checker.warnings
+}
+
+/// Why a block of code is unscheduled
+struct Context {
+    span: Option<Range<usize>>,
+    reason: String,
+}
+
+struct Checker<'a> {
+    /// Code assigned to a variable, that can be executed with `call`
+    functions: HashMap<String, &'a Statements>,
+    processed: &'a Processed,
+    visited: HashSet<String>,
+    reported: HashSet<Range<usize>>,
+    warnings: Vec<Arc<dyn Code>>,
+}
+
+impl<'a> Checker<'a> {
+    fn check(&mut self, statements: &'a Statements, context: &Context) {
+        for statement in statements.content() {
+            match statement {
+                // a function definition is not executed here
+                Statement::AssignGlobal(_, Expression::Code(_), _)
+                | Statement::AssignLocal(_, Expression::Code(_), _) => {}
+                Statement::AssignGlobal(_, expression, _)
+                | Statement::AssignLocal(_, expression, _)
+                | Statement::Expression(expression, _) => {
+                    self.check_expression(expression, context);
+                }
+            }
+        }
+    }
+
+    fn check_expression(&mut self, expression: &'a Expression, context: &Context) {
+        match expression {
+            Expression::UnaryCommand(UnaryCommand::Named(name), child, span) => {
+                let name = name.to_ascii_lowercase();
+                if SUSPENDING.contains(&name.as_str()) {
+                    self.report(expression, span, context);
+                }
+                if name == "call" {
+                    self.check_call(child, context);
+                } else if INLINE_UNARY.contains(&name.as_str()) {
+                    self.check_inline(child, context);
+                } else {
+                    self.check_expression(child, context);
+                }
+            }
+            Expression::UnaryCommand(_, child, _) => self.check_expression(child, context),
+            Expression::BinaryCommand(command, left, right, _) => {
+                let name = command.as_str().to_ascii_lowercase();
+                match command {
+                    BinaryCommand::Named(_) if name == "spawn" => {
+                        self.check_expression(left, context);
+                    }
+                    BinaryCommand::Named(_) if name == "call" => {
+                        self.check_expression(left, context);
+                        self.check_call(right, context);
+                    }
+                    BinaryCommand::Named(_) if INLINE_BINARY.contains(&name.as_str()) => {
+                        self.check_inline(left, context);
+                        self.check_inline(right, context);
+                    }
+                    BinaryCommand::Else | BinaryCommand::Associate => {
+                        self.check_inline(left, context);
+                        self.check_inline(right, context);
+                    }
+                    _ => {
+                        self.check_expression(left, context);
+                        self.check_expression(right, context);
+                    }
+                }
+            }
+            Expression::Array(elements, _) => {
+                for element in elements {
+                    self.check_expression(element, context);
+                }
+            }
+            // code that is not executed immediately may run in any context
+            _ => {}
+        }
+    }
+
+    /// Check an argument that is executed immediately if it is code
+    fn check_inline(&mut self, expression: &'a Expression, context: &Context) {
+        match expression {
+            Expression::Code(statements) => self.check(statements, context),
+            // `if (...) then [{}, {}]`
+            Expression::Array(elements, _) => {
+                for element in elements {
+                    self.check_inline(element, context);
+                }
+            }
+            _ => self.check_expression(expression, context),
+        }
+    }
+
+    /// Check the target of `call`, following functions defined in the same file
+    fn check_call(&mut self, expression: &'a Expression, context: &Context) {
+        if let Expression::Variable(name, _) = expression {
+            let name = name.to_ascii_lowercase();
+            if let Some(function) = self.functions.get(&name).copied() {
+                if self.visited.insert(name) {
+                    self.check(function, context);
+                }
+            }
+            return;
+        }
+        self.check_inline(expression, context);
+    }
+
+    fn report(&mut self, expression: &Expression, span: &Range<usize>, context: &Context) {
+        if !self.reported.insert(span.clone()) {
+            return;
+        }
+        let Expression::UnaryCommand(command, _, _) = expression else {
+            return;
+        };
+        self.warnings.push(Arc::new(SuspendUnscheduled::new(
+            span.clone(),
+            command.as_str().to_string(),
+            context.span.clone(),
+            context.reason.clone(),
+            self.processed,
+        )));
+    }
+}
+
+/// Find code that is provably unscheduled, such as event handlers
+fn entry_point(expression: &Expression) -> Option<(&Statements, Context)> {
+    match expression {
+        Expression::BinaryCommand(BinaryCommand::Named(name), _, right, span)
+            if EVENT_HANDLERS_BINARY.contains(&name.to_ascii_lowercase().as_str()) =>
+        {
+            Some((
+                code_at(right, 1)?,
+                Context {
+                    span: Some(span.clone()),
+                    reason: String::from("event handlers run unscheduled"),
+                },
+            ))
+        }
+        Expression::BinaryCommand(BinaryCommand::Named(name), left, right, _)
+            if name.eq_ignore_ascii_case("call") =>
+        {
+            let Expression::Variable(function, span) = &**right else {
+                return None;
+            };
+            let (_, index) = EVENT_HANDLERS_CBA
+                .iter()
+                .find(|(f, _)| function.eq_ignore_ascii_case(f))?;
+            Some((
+                code_at(left, *index)?,
+                Context {
+                    span: Some(span.clone()),
+                    reason: String::from("event handlers run unscheduled"),
+                },
+            ))
+        }
+        Expression::UnaryCommand(UnaryCommand::Named(name), child, span)
+            if EVENT_HANDLERS_UNARY.contains(&name.to_ascii_lowercase().as_str()) =>
+        {
+            Some((
+                code_at(child, 1)?,
+                Context {
+                    span: Some(span.clone()),
+                    reason: String::from("event handlers run unscheduled"),
+                },
+            ))
+        }
+        Expression::UnaryCommand(UnaryCommand::Named(name), child, span)
+            if name.eq_ignore_ascii_case("isnil") =>
+        {
+            let Expression::Code(code) = &**child else {
+                return None;
+            };
+            Some((
+                code,
+                Context {
+                    span: Some(span.clone()),
+                    reason: String::from("`isNil` runs its code unscheduled"),
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// The code at an index of an array argument
+fn code_at(expression: &Expression, index: usize) -> Option<&Statements> {
+    let Expression::Array(elements, _) = expression else {
+        return None;
+    };
+    let Some(Expression::Code(code)) = elements.get(index) else {
+        return None;
+    };
+    Some(code)
+}
+
+/// Code assigned to variables in this file
+fn functions(statements: &Statements) -> HashMap<String, &Statements> {
+    let mut functions = HashMap::new();
+    for statement in statements.content() {
+        for statement in statement.walk_statements() {
+            if let Statement::AssignGlobal(name, Expression::Code(code), _)
+            | Statement::AssignLocal(name, Expression::Code(code), _) = statement
+            {
+                functions.insert(name.to_ascii_lowercase(), code);
+            }
+        }
+    }
+    functions
+}
+
+/// Whether the file is called by CBA's extended event handlers
+fn is_xeh_file(statements: &Statements, processed: &Processed) -> bool {
+    statements.content().iter().any(|statement| {
+        processed
+            .mapping(statement.span().start)
+            .filter(|map| !map.was_macro())
+            .and_then(|map| processed.source(map.source()))
+            .is_some_and(|(path, _)| {
+                XEH_FILES
+                    .iter()
+                    .any(|xeh| path.filename().eq_ignore_ascii_case(xeh))
+            })
+    })
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use hemtt_common::reporting::{Code, Processed};
+
+use crate::{
+    analyze::codes::saw10_unreachable::Unreachable, BinaryCommand, Expression, Statement,
+    Statements, UnaryCommand,
+};
+
+/// Nular commands that always leave the current scope
+const EXIT_NULAR: &[&str] = &["break", "continue"];
+
+/// Unary commands that always leave the current scope
+const EXIT_UNARY: &[&str] = &["breakout", "breakto", "throw"];
+
+pub fn unreachable(statements: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let mut warnings: Vec<Arc<dyn Code>> = Vec::new();
+    warnings.extend(check_block(statements, processed));
+    for statement in statements.content() {
+        for expression in statement.walk_expressions() {
+            if let Expression::Code(block) = expression {
+                warnings.extend(check_block(block, processed));
+            }
+        }
+    }
+    warnings
+}
+
+/// Reports the statements after the first one that always exits the block
+fn check_block(block: &Statements, processed: &Processed) -> Vec<Arc<dyn Code>> {
+    let content = block.content();
+    let Some((index, exit)) = content
+        .iter()
+        .enumerate()
+        .find_map(|(index, statement)| exit(statement).map(|exit| (index, exit)))
+    else {
+        return Vec::new();
+    };
+    let (Some(first), Some(last)) = (content.get(index + 1), content.last()) else {
+        return Vec::new();
+    };
+    vec![Arc::new(Unreachable::new(
+        first.span().start..last.span().end,
+        exit,
+        processed,
+    ))]
+}
+
+/// The command of a statement that always exits the current scope
+fn exit(statement: &Statement) -> Option<(String, std::ops::Range<usize>)> {
+    let Statement::Expression(expression, _) = statement else {
+        return None;
+    };
+    match expression {
+        Expression::NularCommand(command, span)
+            if EXIT_NULAR.contains(&command.as_str().to_lowercase().as_str()) =>
+        {
+            Some((command.as_str().to_string(), span.clone()))
+        }
+        Expression::UnaryCommand(UnaryCommand::Named(name), _, span)
+            if EXIT_UNARY.contains(&name.to_lowercase().as_str()) =>
+        {
+            Some((name.clone(), span.start..expression.full_span().end))
+        }
+        // `if (true) exitWith { ... }`
+        Expression::BinaryCommand(BinaryCommand::Named(name), if_cmd, _, span)
+            if name.eq_ignore_ascii_case("exitwith")
+                && matches!(
+                    &**if_cmd,
+                    Expression::UnaryCommand(_, condition, _)
+                        if matches!(&**condition, Expression::Boolean(true, _))
+                ) =>
+        {
+            Some((name.clone(), if_cmd.span().start..span.end))
+        }
+        _ => None,
+    }
+}
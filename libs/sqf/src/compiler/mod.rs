@@ -14,7 +14,10 @@ use std::ops::Range;
 use hemtt_common::{error::thiserror, reporting::Processed};
 
 use self::serializer::{Compiled, Constant, Instruction, Instructions, SourceInfo};
-use crate::{Error, Expression, Statement, Statements};
+use crate::{
+    minify::{minify, Minified},
+    Error, Expression, Statement, Statements,
+};
 
 impl Statements {
     /// Converts this statements list into a [`Compiled`].
@@ -23,9 +26,27 @@ impl Statements {
     /// # Errors
     /// [`CompileError`] is returned if the statements list contains an invalid name.
     pub fn compile(&self, processed: &Processed) -> CompileResult<Compiled> {
+        self.compile_with(processed, None)
+    }
+
+    /// Converts this statements list into a [`Compiled`], with minified source strings.
+    /// The offsets of each [`SourceInfo`] point into the minified source, and lines are unchanged.
+    ///
+    /// # Errors
+    /// [`CompileError`] is returned if the statements list contains an invalid name.
+    pub fn compile_minified(&self, processed: &Processed) -> CompileResult<Compiled> {
+        self.compile_with(processed, Some(Minified::new(processed.as_str())))
+    }
+
+    fn compile_with(
+        &self,
+        processed: &Processed,
+        minified: Option<Minified>,
+    ) -> CompileResult<Compiled> {
         let mut ctx = Context {
             constants_cache: Vec::new(),
             names_cache: Vec::new(),
+            minified,
         };
         let entrypoint_code = self.compile_to_instructions(processed, &mut ctx)?;
         let entrypoint_index = ctx.constants_cache.len() as u16;
@@ -55,6 +76,18 @@ impl Statements {
         Ok(self.compile(processed)?.serialize(&mut writer)?)
     }
 
+    /// Compiles this statements list to a writer, with minified source strings.
+    ///
+    /// # Errors
+    /// [`Error`] is returned if the statements list contains an invalid name.
+    pub fn compile_minified_to_writer(
+        &self,
+        processed: &Processed,
+        mut writer: impl std::io::Write,
+    ) -> Result<(), Error> {
+        Ok(self.compile_minified(processed)?.serialize(&mut writer)?)
+    }
+
     pub(crate) fn compile_to_instructions(
         &self,
         processed: &Processed,
@@ -65,7 +98,12 @@ impl Statements {
             statement.compile_instructions(&mut instructions, processed, ctx)?;
         }
 
-        let source_string_index = ctx.add_constant(Constant::String(self.source.clone()))?;
+        let source = if ctx.minified.is_some() {
+            minify(&self.source)
+        } else {
+            self.source.clone()
+        };
+        let source_string_index = ctx.add_constant(Constant::String(source))?;
         Ok(Instructions {
             contents: instructions,
             source_string_index,
@@ -107,7 +145,7 @@ impl Statement {
                 let name_index = ctx.add_name(name)?;
                 instructions.push(Instruction::AssignTo(
                     name_index,
-                    location_to_source(processed, location),
+                    ctx.source_info(processed, location),
                 ));
             }
             Self::AssignLocal(ref name, ref expression, ref location) => {
@@ -115,7 +153,7 @@ impl Statement {
                 let name_index = ctx.add_name(name)?;
                 instructions.push(Instruction::AssignToLocal(
                     name_index,
-                    location_to_source(processed, location),
+                    ctx.source_info(processed, location),
                 ));
             }
             Self::Expression(ref expression, _) => {
@@ -173,14 +211,14 @@ impl Expression {
 
                     instructions.push(Instruction::MakeArray(
                         array_len,
-                        location_to_source(processed, location),
+                        ctx.source_info(processed, location),
                     ));
                 }
                 Self::NularCommand(ref command, ref location) => {
                     let name_index = ctx.add_name(command.as_str())?;
                     instructions.push(Instruction::CallNular(
                         name_index,
-                        location_to_source(processed, location),
+                        ctx.source_info(processed, location),
                     ));
                 }
                 Self::UnaryCommand(ref command, ref expr, ref location) => {
@@ -188,7 +226,7 @@ impl Expression {
                     let name_index = ctx.add_name(command.as_str())?;
                     instructions.push(Instruction::CallUnary(
                         name_index,
-                        location_to_source(processed, location),
+                        ctx.source_info(processed, location),
                     ));
                 }
                 Self::BinaryCommand(ref command, ref expr1, ref expr2, ref location) => {
@@ -197,14 +235,14 @@ impl Expression {
                     let name_index = ctx.add_name(command.as_str())?;
                     instructions.push(Instruction::CallBinary(
                         name_index,
-                        location_to_source(processed, location),
+                        ctx.source_info(processed, location),
                     ));
                 }
                 Self::Variable(ref name, ref location) => {
                     let name_index = ctx.add_name(name)?;
                     instructions.push(Instruction::GetVariable(
                         name_index,
-                        location_to_source(processed, location),
+                        ctx.source_info(processed, location),
                     ));
                 }
                 Self::Code(_) | Self::String(_, _) | Self::Number(_, _) | Self::Boolean(_, _) => {
@@ -264,6 +302,7 @@ type CompileResult<T = ()> = Result<T, CompileError>;
 pub(crate) struct Context {
     constants_cache: Vec<Constant>,
     names_cache: Vec<String>,
+    minified: Option<Minified>,
 }
 
 impl Context {
@@ -271,6 +310,15 @@ impl Context {
         add_or_get_index(&mut self.constants_cache, constant)
     }
 
+    /// Converts a location to a [`SourceInfo`], pointing into the minified source if there is one
+    pub(crate) fn source_info(&self, processed: &Processed, location: &Range<usize>) -> SourceInfo {
+        let mut info = location_to_source(processed, location);
+        if let Some(minified) = &self.minified {
+            info.offset = minified.offset(location.start) as u32;
+        }
+        info
+    }
+
     pub(crate) fn add_name(&mut self, name: &str) -> CompileResult<u16> {
         add_or_get_index(&mut self.names_cache, try_normalize_name(name)?)
     }
@@ -4,6 +4,8 @@ use hemtt_common::error::thiserror;
 pub enum Error {
     #[error(transparent)]
     ParserError(#[from] crate::parser::ParserError),
+    #[error(transparent)]
+    FormatError(#[from] crate::formatter::FormatError),
     #[cfg(feature = "compiler")]
     #[error(transparent)]
     CompileError(#[from] crate::compiler::CompileError),
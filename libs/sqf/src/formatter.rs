@@ -0,0 +1,572 @@
+//! Formats SQF source files
+//!
+//! The formatter works on the tokens of the file before it is preprocessed,
+//! so macros are kept as they were written, and are formatted like commands.
+//! Preprocessor directives are kept exactly as they are.
+
+use hemtt_common::{
+    error::thiserror,
+    project::sqf::{BraceStyle, FormatOptions},
+};
+
+use crate::parser::lexer::{self, Control, Operator, Token};
+
+/// Words that can follow `#` at the start of a line to form a preprocessor directive
+const DIRECTIVES: &[&str] = &[
+    "define", "undef", "include", "if", "ifdef", "ifndef", "else", "endif", "pragma", "line",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum FormatError {
+    #[error("unable to tokenize the file at offset {0}")]
+    Tokenize(usize),
+    #[error("unbalanced brackets at offset {0}")]
+    Unbalanced(usize),
+    #[error("formatting would change the tokens of the file")]
+    Changed,
+}
+
+/// Format an SQF file
+///
+/// # Errors
+/// [`FormatError`] if the file can not be formatted without changing its meaning,
+/// such as when it contains syntax that is only valid after preprocessing
+pub fn format(source: &str, options: &FormatOptions) -> Result<String, FormatError> {
+    // comments and directives are copied as written, so line endings are only converted back at the end
+    let crlf = source.contains("\r\n");
+    let source = source.replace("\r\n", "\n");
+    let chars = source.chars().collect::<Vec<_>>();
+    let items = items(&chars)?;
+    let closers = closers(&items)?;
+    let mut printer = Printer {
+        options,
+        chars: &chars,
+        items: &items,
+        closers: &closers,
+        out: String::new(),
+        line_indent: 0,
+        at_line_start: true,
+        force_newline: false,
+        frames: vec![Frame {
+            control: None,
+            expanded: true,
+            base: 0,
+            indent: 0,
+            last_code: None,
+        }],
+    };
+    printer.print();
+    let out = printer.out;
+    if signature(&items) != signature(&self::items(&out.chars().collect::<Vec<_>>())?) {
+        return Err(FormatError::Changed);
+    }
+    if crlf {
+        Ok(out.replace('\n', "\r\n"))
+    } else {
+        Ok(out)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Directive,
+    Comment { line: bool },
+    Token(Token),
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    kind: Kind,
+    text: String,
+    start: usize,
+    end: usize,
+}
+
+impl Item {
+    const fn control(&self) -> Option<Control> {
+        if let Kind::Token(Token::Control(control)) = &self.kind {
+            Some(*control)
+        } else {
+            None
+        }
+    }
+
+    const fn operator(&self) -> Option<Operator> {
+        if let Kind::Token(Token::Operator(operator)) = &self.kind {
+            Some(*operator)
+        } else {
+            None
+        }
+    }
+
+    const fn is_code(&self) -> bool {
+        matches!(self.kind, Kind::Token(_))
+    }
+
+    const fn is_opener(&self) -> bool {
+        matches!(
+            self.control(),
+            Some(
+                Control::CurlyBracketOpen | Control::SquareBracketOpen | Control::RoundBracketOpen
+            )
+        )
+    }
+
+    const fn is_closer(&self) -> bool {
+        matches!(
+            self.control(),
+            Some(
+                Control::CurlyBracketClose
+                    | Control::SquareBracketClose
+                    | Control::RoundBracketClose
+            )
+        )
+    }
+}
+
+/// Split the file into directives, comments, and tokens, in order
+fn items(chars: &[char]) -> Result<Vec<Item>, FormatError> {
+    let mut blanked = chars.to_vec();
+    let mut directives = Vec::new();
+    let mut line_start = 0;
+    while line_start < chars.len() {
+        let mut start = line_start;
+        while start < chars.len() && chars[start] != '\n' && chars[start].is_whitespace() {
+            start += 1;
+        }
+        let mut end = line_end(chars, line_start);
+        if chars.get(start) == Some(&'#') && is_directive(chars, start + 1) {
+            // directives continue onto the next line when a line ends with `\`
+            while chars[start..end].iter().rev().find(|c| !c.is_whitespace()) == Some(&'\\')
+                && end < chars.len()
+            {
+                end = line_end(chars, end + 1);
+            }
+            let text_end = end
+                - chars[start..end]
+                    .iter()
+                    .rev()
+                    .take_while(|c| c.is_whitespace())
+                    .count();
+            directives.push(Item {
+                kind: Kind::Directive,
+                text: chars[start..text_end].iter().collect(),
+                start,
+                end: text_end,
+            });
+            for c in &mut blanked[start..end] {
+                if *c != '\n' {
+                    *c = ' ';
+                }
+            }
+        }
+        line_start = end + 1;
+    }
+
+    let tokens = lexer::run(blanked.iter().collect::<String>())
+        .map_err(|errors| FormatError::Tokenize(errors.first().map_or(0, |e| e.span().start)))?;
+    let mut items = tokens
+        .into_iter()
+        .map(|(token, span)| {
+            let text = chars[span.clone()].iter().collect::<String>();
+            Item {
+                kind: match token {
+                    Token::Comment(_) => Kind::Comment {
+                        line: text.starts_with("//"),
+                    },
+                    token => Kind::Token(token),
+                },
+                text: text.trim_end().to_string(),
+                start: span.start,
+                end: span.end,
+            }
+        })
+        .collect::<Vec<_>>();
+    // a directive inside of a comment or string is already part of its text
+    directives.retain(|directive| {
+        !items
+            .iter()
+            .any(|item| (item.start..item.end).contains(&directive.start))
+    });
+    items.extend(directives);
+    items.sort_by_key(|item| item.start);
+    Ok(items)
+}
+
+fn line_end(chars: &[char], from: usize) -> usize {
+    chars[from..]
+        .iter()
+        .position(|c| *c == '\n')
+        .map_or(chars.len(), |position| from + position)
+}
+
+fn is_directive(chars: &[char], from: usize) -> bool {
+    let word = chars[from..]
+        .iter()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>();
+    DIRECTIVES.contains(&word.as_str())
+}
+
+/// The index of the matching closer for each opener
+fn closers(items: &[Item]) -> Result<Vec<Option<usize>>, FormatError> {
+    let mut closers = vec![None; items.len()];
+    let mut stack: Vec<usize> = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        if item.is_opener() {
+            stack.push(index);
+        } else if item.is_closer() {
+            let Some(opener) = stack.pop() else {
+                return Err(FormatError::Unbalanced(item.start));
+            };
+            let expected = match items[opener].control() {
+                Some(Control::CurlyBracketOpen) => Control::CurlyBracketClose,
+                Some(Control::SquareBracketOpen) => Control::SquareBracketClose,
+                _ => Control::RoundBracketClose,
+            };
+            if item.control() != Some(expected) {
+                return Err(FormatError::Unbalanced(item.start));
+            }
+            closers[opener] = Some(index);
+        }
+    }
+    if let Some(opener) = stack.pop() {
+        return Err(FormatError::Unbalanced(items[opener].start));
+    }
+    Ok(closers)
+}
+
+/// The text of every item, except semicolons that end the last statement of a code block,
+/// which may be added by the formatter
+fn signature(items: &[Item]) -> Vec<&str> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(index, item)| {
+            item.control() != Some(Control::Terminator)
+                || items[index + 1..]
+                    .iter()
+                    .find(|next| next.is_code())
+                    .and_then(Item::control)
+                    != Some(Control::CurlyBracketClose)
+        })
+        .map(|(_, item)| item.text.as_str())
+        .collect()
+}
+
+/// An open bracket, or the top level of the file
+struct Frame {
+    control: Option<Control>,
+    /// A code block that spans multiple lines
+    expanded: bool,
+    /// The indentation of the line the bracket was opened on
+    base: usize,
+    /// The indentation of lines inside the bracket
+    indent: usize,
+    /// The position in the output after the last token of an unterminated statement
+    last_code: Option<usize>,
+}
+
+impl Frame {
+    const fn is_block(&self) -> bool {
+        matches!(self.control, None | Some(Control::CurlyBracketOpen))
+    }
+}
+
+enum Break {
+    None { space: bool },
+    Line,
+    Blank,
+}
+
+struct Printer<'a> {
+    options: &'a FormatOptions,
+    chars: &'a [char],
+    items: &'a [Item],
+    closers: &'a [Option<usize>],
+    out: String,
+    line_indent: usize,
+    at_line_start: bool,
+    force_newline: bool,
+    frames: Vec<Frame>,
+}
+
+impl Printer<'_> {
+    fn print(&mut self) {
+        let items = self.items;
+        for (index, item) in items.iter().enumerate() {
+            let decision = self.decide(index);
+            let indent = if item.is_closer() {
+                let frame = self.frames.pop().expect("brackets are balanced");
+                if frame.expanded {
+                    self.terminate(&frame);
+                }
+                frame.base
+            } else {
+                self.frames.last().map_or(0, |frame| frame.indent)
+            };
+            match decision {
+                Break::None { space } => {
+                    if space && !self.at_line_start {
+                        self.out.push(' ');
+                    }
+                }
+                Break::Line => self.newline(false),
+                Break::Blank => self.newline(true),
+            }
+            if self.at_line_start {
+                if item.kind != Kind::Directive {
+                    self.out
+                        .push_str(&" ".repeat(indent * self.options.indent()));
+                }
+                self.line_indent = indent;
+                self.at_line_start = false;
+            }
+            self.out.push_str(&item.text);
+            self.after(index);
+        }
+        let trimmed = self.out.trim_end().len();
+        self.out.truncate(trimmed);
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+    }
+
+    /// Update the state after an item is written
+    fn after(&mut self, index: usize) {
+        let items = self.items;
+        let item = &items[index];
+        match item.kind {
+            Kind::Directive | Kind::Comment { line: true } => {
+                self.force_newline = true;
+                return;
+            }
+            Kind::Comment { line: false } => return,
+            Kind::Token(_) => {}
+        }
+        let position = self.out.len();
+        let frame = self.frames.last_mut().expect("top level is never closed");
+        if item.control() == Some(Control::Terminator) {
+            frame.last_code = None;
+            if frame.is_block() && frame.expanded {
+                self.force_newline = true;
+            }
+            return;
+        }
+        frame.last_code = Some(position);
+        if item.is_opener() {
+            let expanded = self.is_expanded(index);
+            self.frames.push(Frame {
+                control: item.control(),
+                expanded,
+                base: self.line_indent,
+                indent: self.line_indent + 1,
+                last_code: None,
+            });
+            if expanded {
+                self.force_newline = true;
+            }
+        }
+    }
+
+    /// Add a semicolon after the last statement of an expanded block
+    fn terminate(&mut self, frame: &Frame) {
+        if !self.options.trailing_semicolons() {
+            return;
+        }
+        if let Some(position) = frame.last_code {
+            self.out.insert(position, ';');
+        }
+    }
+
+    fn newline(&mut self, blank: bool) {
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if !self.out.is_empty() {
+            self.out.push('\n');
+            if blank && !self.out.ends_with("\n\n") {
+                self.out.push('\n');
+            }
+        }
+        self.at_line_start = true;
+        self.force_newline = false;
+    }
+
+    /// Whether a code block spans multiple lines in the original file
+    fn is_expanded(&self, index: usize) -> bool {
+        let item = &self.items[index];
+        if item.control() != Some(Control::CurlyBracketOpen) {
+            return false;
+        }
+        let Some(closer) = self.closers[index] else {
+            return false;
+        };
+        self.chars[item.start..self.items[closer].start].contains(&'\n')
+    }
+
+    /// The number of newlines between two items in the original file
+    fn newlines(&self, index: usize) -> usize {
+        if index == 0 {
+            return 0;
+        }
+        self.chars[self.items[index - 1].end..self.items[index].start]
+            .iter()
+            .filter(|c| **c == '\n')
+            .count()
+    }
+
+    fn decide(&self, index: usize) -> Break {
+        if index == 0 {
+            return Break::None { space: false };
+        }
+        let item = &self.items[index];
+        let prev = &self.items[index - 1];
+        let newlines = self.newlines(index);
+        let line = || {
+            if newlines > 1 && !prev.is_opener() && !item.is_closer() {
+                Break::Blank
+            } else {
+                Break::Line
+            }
+        };
+        if item.kind == Kind::Directive {
+            return line();
+        }
+        // comments stay on the line they were written on
+        if matches!(item.kind, Kind::Comment { .. }) && newlines == 0 {
+            return Break::None { space: true };
+        }
+        if item.control() == Some(Control::CurlyBracketClose)
+            && self.frames.last().is_some_and(|frame| frame.expanded)
+        {
+            return Break::Line;
+        }
+        if self.force_newline {
+            return line();
+        }
+        let after_opener_or_separator = matches!(
+            prev.control(),
+            Some(Control::RoundBracketOpen | Control::SquareBracketOpen | Control::Separator)
+        );
+        if item.control() == Some(Control::CurlyBracketOpen)
+            && prev.is_code()
+            && !after_opener_or_separator
+            && self.is_expanded(index)
+        {
+            return match self.options.braces() {
+                BraceStyle::SameLine => Break::None { space: true },
+                BraceStyle::NextLine => Break::Line,
+            };
+        }
+        if let Kind::Token(Token::Identifier(name)) = &item.kind {
+            if prev.control() == Some(Control::CurlyBracketClose) && self.closed_expanded(index - 1)
+            {
+                return match self.options.braces() {
+                    BraceStyle::NextLine if name.eq_ignore_ascii_case("else") => Break::Line,
+                    _ => Break::None { space: true },
+                };
+            }
+        }
+        if newlines > 0 {
+            return line();
+        }
+        Break::None {
+            space: self.space_between(index),
+        }
+    }
+
+    /// Whether the item is a `}` that closed an expanded block
+    fn closed_expanded(&self, index: usize) -> bool {
+        self.closers
+            .iter()
+            .position(|closer| *closer == Some(index))
+            .is_some_and(|opener| self.is_expanded(opener))
+    }
+
+    /// Whether a `+` or `-` is a unary command
+    fn is_unary(&self, index: usize) -> bool {
+        let item = &self.items[index];
+        if !matches!(item.operator(), Some(Operator::Add | Operator::Sub)) {
+            return item.operator() == Some(Operator::Not);
+        }
+        let Some(prev) = self.items[..index].iter().rev().find(|item| item.is_code()) else {
+            return true;
+        };
+        match &prev.kind {
+            Kind::Token(Token::Operator(_)) => true,
+            Kind::Token(Token::Control(control)) => !matches!(
+                control,
+                Control::RoundBracketClose
+                    | Control::SquareBracketClose
+                    | Control::CurlyBracketClose
+            ),
+            // `round -1`, where a unary command is followed by a negative number
+            Kind::Token(Token::Identifier(name)) => {
+                !name.starts_with('_')
+                    && prev.end < item.start
+                    && self.chars.get(item.end).is_some_and(|c| !c.is_whitespace())
+            }
+            _ => false,
+        }
+    }
+
+    fn space_between(&self, index: usize) -> bool {
+        let item = &self.items[index];
+        let prev = &self.items[index - 1];
+        if !item.is_code() || !prev.is_code() {
+            return true;
+        }
+        match item.control() {
+            Some(
+                Control::Separator
+                | Control::Terminator
+                | Control::SquareBracketClose
+                | Control::RoundBracketClose,
+            ) => return false,
+            Some(Control::CurlyBracketClose) => {
+                return prev.control() != Some(Control::CurlyBracketOpen)
+            }
+            _ => {}
+        }
+        match prev.control() {
+            Some(Control::SquareBracketOpen | Control::RoundBracketOpen) => return false,
+            Some(Control::CurlyBracketOpen | Control::Separator | Control::Terminator) => {
+                return true
+            }
+            _ => {}
+        }
+        if self.is_unary(index - 1) {
+            return false;
+        }
+        if item.operator() == Some(Operator::Associate) {
+            return false;
+        }
+        if prev.operator() == Some(Operator::Associate)
+            || item.operator() == Some(Operator::Assign)
+            || prev.operator() == Some(Operator::Assign)
+        {
+            return true;
+        }
+        if (item.operator().is_some() && !self.is_unary(index)) || prev.operator().is_some() {
+            return self.options.binary_spaces();
+        }
+        if matches!(
+            item.control(),
+            Some(Control::RoundBracketOpen | Control::SquareBracketOpen)
+        ) {
+            // macros such as `GVAR(name)` stay attached to their arguments
+            if let Kind::Token(Token::Identifier(name)) = &prev.kind {
+                return prev.end < item.start || !is_macro_name(name);
+            }
+        }
+        true
+    }
+}
+
+/// Whether a name follows the convention for macros, `UPPER_CASE`
+fn is_macro_name(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
@@ -1,11 +1,15 @@
 #[cfg(feature = "compiler")]
 pub mod compiler;
 #[cfg(feature = "parser")]
+pub mod formatter;
+#[cfg(feature = "parser")]
 pub mod parser;
 
 pub mod analyze;
 mod error;
+pub mod minify;
 mod misc;
+mod serialize;
 
 use std::ops::Range;
 
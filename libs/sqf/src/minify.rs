@@ -0,0 +1,179 @@
+//! Minifies SQF source for release builds
+//!
+//! Comments are removed and whitespace is collapsed, but every line break is kept,
+//! so line numbers reported by script errors still point at the original line.
+//! Strings are copied as they are, and preprocessor directives only have their
+//! whitespace collapsed, as removing it could change the meaning of a macro.
+
+/// Characters that never need whitespace after them
+const NO_SPACE_AFTER: &[char] = &[';', ',', '(', '[', '{', ']', '}'];
+/// Characters that never need whitespace before them
+const NO_SPACE_BEFORE: &[char] = &[';', ',', ')', ']', '{', '}'];
+
+#[must_use]
+/// Minify SQF source, either as written or after preprocessing
+pub fn minify(source: &str) -> String {
+    Minified::new(source).text
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Minified source, with a map from offsets in the original source
+pub struct Minified {
+    text: String,
+    offsets: Vec<usize>,
+}
+
+impl Minified {
+    #[must_use]
+    /// Minify SQF source, keeping where each character of the original source ended up
+    pub fn new(source: &str) -> Self {
+        let chars = source.chars().collect::<Vec<_>>();
+        let mut minifier = Minifier {
+            text: String::with_capacity(source.len()),
+            length: 0,
+            offsets: Vec::with_capacity(chars.len() + 1),
+            last: None,
+            space: false,
+            line_start: true,
+            directive: false,
+        };
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            match c {
+                '\n' => {
+                    minifier.skip();
+                    minifier.newline();
+                    i += 1;
+                }
+                '/' if chars.get(i + 1) == Some(&'/') => {
+                    while i < chars.len() && chars[i] != '\n' {
+                        minifier.skip();
+                        i += 1;
+                    }
+                    minifier.space = true;
+                }
+                '/' if chars.get(i + 1) == Some(&'*') => {
+                    let end = (i + 2..chars.len().saturating_sub(1))
+                        .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                        .map_or(chars.len(), |j| j + 2);
+                    while i < end {
+                        minifier.skip();
+                        if chars[i] == '\n' {
+                            minifier.newline();
+                        }
+                        i += 1;
+                    }
+                    minifier.space = true;
+                }
+                ' ' | '\t' | '\r' => {
+                    minifier.skip();
+                    minifier.space = true;
+                    i += 1;
+                }
+                '"' | '\'' => {
+                    minifier.push(c);
+                    i += 1;
+                    while i < chars.len() {
+                        minifier.copy(chars[i]);
+                        i += 1;
+                        if chars[i - 1] == c {
+                            break;
+                        }
+                    }
+                }
+                _ => {
+                    if c == '#' && minifier.line_start {
+                        minifier.directive = true;
+                    }
+                    minifier.push(c);
+                    i += 1;
+                }
+            }
+        }
+        minifier.offsets.push(minifier.length);
+        Self {
+            text: minifier.text,
+            offsets: minifier.offsets,
+        }
+    }
+
+    #[must_use]
+    /// The minified source
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    #[must_use]
+    /// Converts a character offset in the original source to one in the minified source
+    pub fn offset(&self, offset: usize) -> usize {
+        self.offsets
+            .get(offset)
+            .or_else(|| self.offsets.last())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+struct Minifier {
+    text: String,
+    /// Length of `text` in characters
+    length: usize,
+    offsets: Vec<usize>,
+    last: Option<char>,
+    /// Whitespace or a comment was skipped since the last character
+    space: bool,
+    /// Nothing has been written on the current line yet
+    line_start: bool,
+    /// The current line is part of a preprocessor directive
+    directive: bool,
+}
+
+impl Minifier {
+    /// Skips a character of the original source
+    fn skip(&mut self) {
+        self.offsets.push(self.length);
+    }
+
+    /// Writes a line break, which was already recorded as skipped
+    fn newline(&mut self) {
+        self.directive = self.directive && self.last == Some('\\');
+        self.write('\n');
+        self.space = false;
+        self.line_start = true;
+    }
+
+    /// Writes a character, with a single space before it if one is needed
+    fn push(&mut self, c: char) {
+        if self.space && !self.line_start && self.needs_space(c) {
+            self.write(' ');
+        }
+        self.copy(c);
+    }
+
+    /// Writes a character exactly as it was
+    fn copy(&mut self, c: char) {
+        self.offsets.push(self.length);
+        self.write(c);
+        self.space = false;
+        self.line_start = c == '\n';
+    }
+
+    fn write(&mut self, c: char) {
+        self.text.push(c);
+        self.length += 1;
+        self.last = Some(c);
+    }
+
+    fn needs_space(&self, next: char) -> bool {
+        if self.directive {
+            return true;
+        }
+        match self.last {
+            // a macro call such as `GVAR(list)` would join the word after it
+            Some(')') => next.is_alphanumeric() || next == '_',
+            Some(last) if NO_SPACE_AFTER.contains(&last) => false,
+            _ => !NO_SPACE_BEFORE.contains(&next),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Commands and functions that should no longer be used.
+
+use arma3_wiki::model::Command;
+
+/// Deprecated commands and functions maintained by HEMTT, in addition to the wiki.
+///
+/// `(name, replacement, reason)`
+pub const DEPRECATED_HEMTT: &[(&str, Option<&str>, &str)] = &[
+    (
+        "setVehicleInit",
+        Some("remoteExec"),
+        "vehicle init commands were removed in Arma 3 1.40 for security reasons",
+    ),
+    (
+        "processInitCommands",
+        Some("remoteExec"),
+        "vehicle init commands were removed in Arma 3 1.40 for security reasons",
+    ),
+    (
+        "clearVehicleInit",
+        None,
+        "vehicle init commands were removed in Arma 3 1.40 for security reasons",
+    ),
+    (
+        "BIS_fnc_MP",
+        Some("remoteExec"),
+        "BIS_fnc_MP is a wrapper around remoteExec and is kept only for backwards compatibility",
+    ),
+    (
+        "BIS_fnc_MPexec",
+        Some("remoteExec"),
+        "BIS_fnc_MPexec is kept only for backwards compatibility",
+    ),
+    (
+        "BIS_fnc_param",
+        Some("param"),
+        "the `param` and `params` commands are faster",
+    ),
+    (
+        "BIS_fnc_selectRandom",
+        Some("selectRandom"),
+        "the `selectRandom` command is faster",
+    ),
+    (
+        "BIS_fnc_arrayPush",
+        Some("pushBack"),
+        "the `pushBack` command is faster",
+    ),
+    (
+        "BIS_fnc_arrayPushStack",
+        Some("append"),
+        "the `append` command is faster",
+    ),
+    (
+        "BIS_fnc_areEqual",
+        Some("isEqualTo"),
+        "the `isEqualTo` command is faster",
+    ),
+    (
+        "BIS_fnc_inTrigger",
+        Some("inArea"),
+        "the `inArea` command is faster",
+    ),
+    (
+        "BIS_fnc_distance2D",
+        Some("distance2D"),
+        "the `distance2D` command is faster",
+    ),
+    (
+        "difficultyEnabled",
+        Some("difficultyOption"),
+        "difficulty settings were reworked in Arma 3 1.56",
+    ),
+];
+
+/// Phrases in a wiki description that mark a command as deprecated
+const WIKI_PHRASES: &[&str] = &[
+    "command is deprecated",
+    "command is obsolete",
+    "command is broken",
+    "command is no longer supported",
+    "command has been deprecated",
+];
+
+/// Groups on the wiki that mark a command as deprecated
+const WIKI_GROUPS: &[&str] = &["broken", "deprecated", "obsolete"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A command or function that should no longer be used
+pub struct Deprecation {
+    replacement: Option<String>,
+    reason: Option<String>,
+}
+
+impl Deprecation {
+    #[must_use]
+    pub const fn new(replacement: Option<String>, reason: Option<String>) -> Self {
+        Self {
+            replacement,
+            reason,
+        }
+    }
+
+    #[must_use]
+    /// The command or function to use instead, if any
+    pub fn replacement(&self) -> Option<&str> {
+        self.replacement.as_deref()
+    }
+
+    #[must_use]
+    /// Why the command or function is deprecated, if known
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    #[must_use]
+    /// Check the wiki metadata of a command for a deprecation notice
+    pub fn from_wiki(command: &Command) -> Option<Self> {
+        let description = command.description().to_lowercase();
+        let deprecated = command.groups().iter().any(|group| {
+            let group = group.to_lowercase();
+            WIKI_GROUPS.iter().any(|g| group.contains(g))
+        }) || WIKI_PHRASES.iter().any(|p| description.contains(p));
+        if !deprecated {
+            return None;
+        }
+        Some(Self {
+            replacement: wiki_replacement(command.description()),
+            reason: Some(String::from("marked as deprecated on the wiki")),
+        })
+    }
+}
+
+/// Find `[[command]] instead` in a wiki description
+fn wiki_replacement(description: &str) -> Option<String> {
+    let instead = description.to_ascii_lowercase().find("instead")?;
+    let before = &description[..instead];
+    let start = before.rfind("[[")? + 2;
+    let end = start + before[start..].find("]]")?;
+    let link = &before[start..end];
+    let name = link.split('|').next().unwrap_or(link).trim();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
@@ -1,13 +1,18 @@
 //! Allows customization of the commands list at runtime in order to facilitate forwards-compatibility.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use arma3_wiki::{
     model::{Call, Version},
     Wiki,
 };
+use hemtt_common::project::sqf::{Command, Syntax};
 use tracing::{trace, warn};
 
+use self::deprecated::{Deprecation, DEPRECATED_HEMTT};
+
+pub mod deprecated;
+
 /// The list of commands that are valid nular command constants for the compiler.
 pub const NULAR_COMMANDS_CONSTANTS: &[&str] = &[
     // NOTE: `netobjnull` is not included because it's broken
@@ -48,6 +53,9 @@ pub struct Database {
     nular_commands: HashSet<String>,
     unary_commands: HashSet<String>,
     binary_commands: HashSet<String>,
+    deprecated: HashMap<String, Deprecation>,
+    custom_commands: HashMap<String, Command>,
+    custom_versions: HashMap<String, Version>,
     wiki: Wiki,
 }
 
@@ -55,11 +63,15 @@ impl Database {
     #[must_use]
     /// An empty database with no entries.
     pub fn new() -> Self {
+        let wiki = load_wiki();
         Self {
             nular_commands: HashSet::new(),
             unary_commands: HashSet::new(),
             binary_commands: HashSet::new(),
-            wiki: load_wiki(),
+            deprecated: load_deprecated(&wiki),
+            custom_commands: HashMap::new(),
+            custom_versions: HashMap::new(),
+            wiki,
         }
     }
 
@@ -81,6 +93,37 @@ impl Database {
         };
     }
 
+    /// Adds a command that is not part of the base game, such as one declared by a project
+    pub fn add_custom_command(&mut self, command: &Command) {
+        let name = command.name();
+        if !is_valid_name(name) {
+            warn!("Ignoring custom command with invalid name: {}", name);
+            return;
+        }
+        match command.syntax() {
+            Syntax::Nular => self.add_nular_command(name),
+            Syntax::Unary => self.add_unary_command(name),
+            Syntax::Binary => self.add_binary_command(name),
+        }
+        if let Some(since) = command.since() {
+            match Version::from_wiki(since) {
+                Ok(version) => {
+                    self.custom_versions
+                        .insert(name.to_ascii_lowercase(), version);
+                }
+                Err(e) => warn!("Ignoring version of custom command `{}`: {}", name, e),
+            }
+        }
+        self.custom_commands
+            .insert(name.to_ascii_lowercase(), command.clone());
+    }
+
+    #[must_use]
+    /// Returns a command that is not part of the base game, if it was added with [`Database::add_custom_command`]
+    pub fn custom_command(&self, command: &str) -> Option<&Command> {
+        self.custom_commands.get(&command.to_ascii_lowercase())
+    }
+
     #[must_use]
     pub fn has_nular_command(&self, command: &str) -> bool {
         self.nular_commands.contains(&command.to_ascii_lowercase())
@@ -124,12 +167,19 @@ impl Database {
         &self.wiki
     }
 
+    #[must_use]
+    /// Returns the deprecation of a command or function, if it is deprecated
+    pub fn deprecation(&self, command: &str) -> Option<&Deprecation> {
+        self.deprecated.get(&command.to_ascii_lowercase())
+    }
+
     #[must_use]
     pub fn command_version(&self, command: &str) -> Option<&Version> {
         self.wiki
             .commands()
             .get(command)
             .and_then(|c| c.since().arma_3())
+            .or_else(|| self.custom_versions.get(&command.to_ascii_lowercase()))
     }
 }
 
@@ -168,6 +218,9 @@ impl Default for Database {
             nular_commands,
             unary_commands,
             binary_commands,
+            deprecated: load_deprecated(&wiki),
+            custom_commands: HashMap::new(),
+            custom_versions: HashMap::new(),
             wiki,
         }
     }
@@ -223,3 +276,22 @@ fn load_wiki() -> Wiki {
         |wiki| wiki,
     )
 }
+
+fn load_deprecated(wiki: &Wiki) -> HashMap<String, Deprecation> {
+    let mut deprecated = HashMap::new();
+    for command in wiki.commands().values() {
+        if let Some(deprecation) = Deprecation::from_wiki(command) {
+            deprecated.insert(command.name().to_ascii_lowercase(), deprecation);
+        }
+    }
+    for (name, replacement, reason) in DEPRECATED_HEMTT {
+        deprecated.insert(
+            name.to_ascii_lowercase(),
+            Deprecation::new(
+                replacement.map(std::string::ToString::to_string),
+                Some((*reason).to_string()),
+            ),
+        );
+    }
+    deprecated
+}
@@ -18,7 +18,8 @@ use hemtt_common::reporting::{Code, Processed};
 ///
 /// # Errors
 /// [`ParserError::LexingError`] if the input string contains invalid tokens.
-/// [`ParserError::ParsingError`] if the input string contains invalid syntax.
+/// [`ParserError::ParsingError`] if the input string contains invalid syntax,
+/// along with the statements that could still be parsed.
 pub fn run(database: &Database, processed: &Processed) -> Result<Statements, ParserError> {
     let mut tokens = self::lexer::run(processed.as_str()).map_err(|e| {
         let mut errors: Vec<Arc<dyn Code>> = Vec::new();
@@ -32,44 +33,69 @@ pub fn run(database: &Database, processed: &Processed) -> Result<Statements, Par
     })?;
     self::lexer::strip_comments(&mut tokens);
     self::lexer::strip_noop(&mut tokens);
-    let mut statements = run_for_tokens(database, processed, tokens).map_err(|e| {
-        let mut errors: Vec<Arc<dyn Code>> = Vec::new();
-        for e in e {
-            errors.push(Arc::new(codes::spe2_unparseable::UnparseableSyntax::new(
-                e.span(),
-                processed,
-            )));
+    let (statements, errors) = run_for_tokens(database, processed, tokens);
+    let statements = statements.map(|s| s.with_source(processed.as_str().to_string()));
+    if errors.is_empty() {
+        if let Some(statements) = statements {
+            return Ok(statements);
         }
-        ParserError::ParsingError(errors)
-    })?;
-    statements.source = processed.as_str().to_string();
-    Ok(statements)
+    }
+    let mut codes: Vec<Arc<dyn Code>> = Vec::new();
+    let mut spans = Vec::new();
+    for e in errors {
+        if spans.contains(&e.span()) {
+            continue;
+        }
+        spans.push(e.span());
+        codes.push(Arc::new(codes::spe2_unparseable::UnparseableSyntax::new(
+            e.span(),
+            processed,
+        )));
+    }
+    Err(ParserError::ParsingError(codes, statements))
 }
 
 #[allow(clippy::range_plus_one)] // chumsky problem
 /// Parses a list of tokens into a list of statements.
 ///
-/// # Errors
-/// [`ParserError::ParsingError`] if the input string contains invalid syntax.
+/// Statements that can not be parsed are skipped up to the next `;` or `}`,
+/// so every independent syntax error is returned alongside the statements
+/// that could be parsed.
 pub fn run_for_tokens<I>(
     database: &Database,
     processed: &Processed,
     input: I,
-) -> Result<Statements, Vec<Simple<Token>>>
+) -> (Option<Statements>, Vec<Simple<Token>>)
 where
     I: IntoIterator<Item = (Token, Range<usize>)>,
 {
     let len = processed.as_str().len();
-    let statements = parser(database).parse(Stream::from_iter(len..len + 1, input.into_iter()))?;
-    Ok(statements)
+    parser(database).parse_recovery(Stream::from_iter(len..len + 1, input.into_iter()))
 }
 
 fn parser(database: &Database) -> impl Parser<Token, Statements, Error = Simple<Token>> + '_ {
-    statements(database).then_ignore(end())
+    let statements = statements(database);
+    // a stray `}` at the top level ends the current block early, report it and keep going
+    let stray_close = just(Token::Control(Control::CurlyBracketClose))
+        .validate(|_, span, emit| emit(Simple::custom(span, "unexpected `}`")));
+    statements
+        .clone()
+        .then(stray_close.ignore_then(statements).repeated())
+        .then_ignore(end())
+        .map(|(first, rest)| Statements {
+            content: first
+                .content
+                .into_iter()
+                .chain(rest.into_iter().flat_map(|s| s.content))
+                .collect(),
+            source: String::new(),
+        })
 }
 
 #[allow(clippy::too_many_lines)]
-fn statements(database: &Database) -> impl Parser<Token, Statements, Error = Simple<Token>> + '_ {
+fn statements(
+    database: &Database,
+) -> impl Parser<Token, Statements, Error = Simple<Token>> + Clone + '_ {
     recursive(|statements| {
         let expression = recursive(|expression| {
             let value = select! { |span|
@@ -214,17 +240,54 @@ fn statements(database: &Database) -> impl Parser<Token, Statements, Error = Sim
                     Statement::AssignGlobal(variable, expression, span)
                 }
             });
-        assignment
+        // a statement must end at a statement boundary, anything else is skipped up to the next one
+        let boundary = just(Token::Control(Control::Terminator))
+            .ignored()
+            .or(just(Token::Control(Control::CurlyBracketClose)).ignored())
+            .or(end())
+            .rewind();
+        let statement = assignment
             .or(expression.map_with_span(Statement::Expression))
+            .then_ignore(boundary)
+            .map(Some);
+        let invalid = invalid_statement().validate(|(), span, emit| {
+            emit(Simple::custom(span, "unparseable syntax"));
+            None
+        });
+        // `choice` rather than `or`, so a statement that recovered from an error is still preferred
+        choice((statement, invalid))
             .separated_by(just(Token::Control(Control::Terminator)))
             .allow_trailing()
             .map(|content| Statements {
-                content,
+                content: content.into_iter().flatten().collect(),
                 source: String::new(),
             })
     })
 }
 
+/// Matches any tokens up to the next statement boundary, keeping code blocks balanced
+fn invalid_statement() -> impl Parser<Token, (), Error = Simple<Token>> + Clone {
+    let tree = recursive(|tree| {
+        let block = tree
+            .or(just(Token::Control(Control::Terminator)).ignored())
+            .repeated()
+            .delimited_by(
+                just(Token::Control(Control::CurlyBracketOpen)),
+                just(Token::Control(Control::CurlyBracketClose)),
+            )
+            .ignored();
+        let token = filter(|token| {
+            !matches!(
+                token,
+                Token::Control(Control::Terminator | Control::CurlyBracketClose)
+            )
+        })
+        .ignored();
+        block.or(token)
+    });
+    tree.repeated().at_least(1).ignored()
+}
+
 fn apply_binary_command(
     base: impl Parser<Token, Expression, Error = Simple<Token>> + Clone,
     locate: impl Fn(BinaryCommand, Range<usize>) -> (BinaryCommand, Range<usize>),
@@ -276,14 +339,24 @@ pub enum ParserError {
     #[error("lexing error {0:?}")]
     LexingError(Vec<Arc<dyn Code>>),
     #[error("parsing error")]
-    ParsingError(Vec<Arc<dyn Code>>),
+    /// The syntax errors, and the statements that could still be parsed
+    ParsingError(Vec<Arc<dyn Code>>, Option<Statements>),
 }
 
 impl ParserError {
     #[must_use]
     pub fn codes(&self) -> &[Arc<dyn Code>] {
         match self {
-            Self::ParsingError(codes) | Self::LexingError(codes) => codes,
+            Self::ParsingError(codes, _) | Self::LexingError(codes) => codes,
+        }
+    }
+
+    #[must_use]
+    /// The statements that could still be parsed, if any
+    pub const fn partial(&self) -> Option<&Statements> {
+        match self {
+            Self::ParsingError(_, statements) => statements.as_ref(),
+            Self::LexingError(_) => None,
         }
     }
 }
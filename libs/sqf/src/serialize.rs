@@ -0,0 +1,90 @@
+//! Serializes the syntax tree with named fields, for tools built on the parser
+//!
+//! Every statement and expression is an object with a `kind` and a `span`,
+//! commands are serialized as they are written
+
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use crate::{Expression, Statement, Statements};
+
+/// The statements, without the source that is kept for compiling
+impl Serialize for Statements {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.content().serialize(serializer)
+    }
+}
+
+impl Serialize for Statement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            Self::AssignGlobal(name, expression, _) | Self::AssignLocal(name, expression, _) => {
+                map.serialize_entry(
+                    "kind",
+                    if matches!(self, Self::AssignGlobal(..)) {
+                        "AssignGlobal"
+                    } else {
+                        "AssignLocal"
+                    },
+                )?;
+                map.serialize_entry("name", name)?;
+                map.serialize_entry("expression", expression)?;
+            }
+            Self::Expression(expression, _) => {
+                map.serialize_entry("kind", "Expression")?;
+                map.serialize_entry("expression", expression)?;
+            }
+        }
+        map.serialize_entry("span", &self.span())?;
+        map.end()
+    }
+}
+
+impl Serialize for Expression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            Self::Code(statements) => {
+                map.serialize_entry("kind", "Code")?;
+                map.serialize_entry("statements", statements)?;
+            }
+            Self::String(value, _) => {
+                map.serialize_entry("kind", "String")?;
+                map.serialize_entry("value", value)?;
+            }
+            Self::Number(value, _) => {
+                map.serialize_entry("kind", "Number")?;
+                map.serialize_entry("value", &value.0)?;
+            }
+            Self::Boolean(value, _) => {
+                map.serialize_entry("kind", "Boolean")?;
+                map.serialize_entry("value", value)?;
+            }
+            Self::Array(elements, _) => {
+                map.serialize_entry("kind", "Array")?;
+                map.serialize_entry("elements", elements)?;
+            }
+            Self::NularCommand(command, _) => {
+                map.serialize_entry("kind", "NularCommand")?;
+                map.serialize_entry("command", command.as_str())?;
+            }
+            Self::UnaryCommand(command, right, _) => {
+                map.serialize_entry("kind", "UnaryCommand")?;
+                map.serialize_entry("command", command.as_str())?;
+                map.serialize_entry("right", right)?;
+            }
+            Self::BinaryCommand(command, left, right, _) => {
+                map.serialize_entry("kind", "BinaryCommand")?;
+                map.serialize_entry("command", command.as_str())?;
+                map.serialize_entry("left", left)?;
+                map.serialize_entry("right", right)?;
+            }
+            Self::Variable(name, _) => {
+                map.serialize_entry("kind", "Variable")?;
+                map.serialize_entry("name", name)?;
+            }
+        }
+        map.serialize_entry("span", &self.span())?;
+        map.end()
+    }
+}
@@ -1,6 +1,10 @@
 use std::io::Read;
 
-use hemtt_common::{reporting::WorkspaceFiles, workspace::LayerType};
+use hemtt_common::{
+    addons::{Addon, Location},
+    reporting::WorkspaceFiles,
+    workspace::LayerType,
+};
 use hemtt_preprocessor::Processor;
 use hemtt_sqf::{analyze::analyze, parser::database::Database};
 
@@ -47,7 +51,7 @@ fn test_analyze(dir: &str) {
             let expected = String::from_utf8_lossy(&expected).replace('\r', "");
             assert_eq!(stdout, expected);
         }
-        Err(hemtt_sqf::parser::ParserError::ParsingError(e)) => {
+        Err(hemtt_sqf::parser::ParserError::ParsingError(e, _)) => {
             for error in e {
                 println!(
                     "{}",
@@ -65,3 +69,50 @@ analyze!(saa2_find_in_str);
 analyze!(saa3_typename);
 analyze!(saa4_str_format);
 analyze!(saa5_select_parse_number);
+analyze!(saa6_call_compile_format);
+analyze!(saa7_for_count);
+analyze!(saa8_if_bool);
+analyze!(saa9_is_nil_local);
+analyze!(saa10_count_empty);
+analyze!(saw1_deprecated);
+analyze!(saw2_suspend_unscheduled);
+analyze!(saw3_params_invalid);
+analyze!(saw4_params_unused);
+analyze!(saw5_params_arity);
+analyze!(saw6_format_missing_argument);
+analyze!(saw7_format_unused_argument);
+analyze!(saw8_format_invalid_placeholder);
+analyze!(saw9_remote_exec_dynamic);
+analyze!(saw10_unreachable);
+analyze!(saw11_null_compare);
+analyze!(suppress);
+
+#[test]
+fn format_localize() {
+    let root = std::path::PathBuf::from(ROOT).join("format_localize");
+    let addon = Addon::new(&root, "main".to_string(), Location::Addons).unwrap();
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&root, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("addons/main/source.sqf").unwrap();
+    let processed = Processor::run(&source).unwrap();
+    let database = Database::default();
+    let sqf = hemtt_sqf::parser::run(&database, &processed).unwrap();
+    let (warnings, _) = analyze(&sqf, None, &processed, Some(&addon), &database);
+    // the format is read from the addon's stringtable, keys are not case sensitive
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|warning| (warning.ident(), warning.message()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "SAW6",
+                "`format` uses `%2`, but is given 1 argument".to_string()
+            ),
+            ("SAW7", "argument 2 of `format` is never used".to_string()),
+            ("SAW8", "`%s` is not a valid placeholder".to_string()),
+        ]
+    );
+}
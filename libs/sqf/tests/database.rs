@@ -0,0 +1,84 @@
+use std::{path::PathBuf, str::FromStr};
+
+use hemtt_common::{
+    project::{
+        sqf::{ArgumentType, Syntax},
+        ProjectConfig,
+    },
+    workspace::LayerType,
+};
+use hemtt_preprocessor::Processor;
+use hemtt_sqf::{analyze::analyze, parser::database::Database};
+
+const ROOT: &str = "tests/database/";
+
+#[test]
+fn database_custom_commands() {
+    let folder = PathBuf::from(ROOT).join("custom_commands");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.sqf").unwrap();
+    let processed = Processor::run(&source).unwrap();
+    assert!(hemtt_sqf::parser::run(&Database::default(), &processed).is_err());
+
+    let project = ProjectConfig::from_file(&folder.join("project.toml")).unwrap();
+    let mut database = Database::default();
+    for command in project.sqf().commands() {
+        database.add_custom_command(command);
+    }
+    assert!(database.has_nular_command("interceptversion"));
+    assert!(database.has_unary_command("interceptCall"));
+    assert!(database.has_binary_command("interceptSend"));
+    assert_eq!(
+        database
+            .command_version("interceptCall")
+            .unwrap()
+            .to_string(),
+        "2.14"
+    );
+    let send = database.custom_command("interceptSend").unwrap();
+    assert_eq!(send.syntax(), Syntax::Binary);
+    assert_eq!(send.left(), Some(ArgumentType::Object));
+    assert_eq!(send.right(), Some(ArgumentType::String));
+    let parsed = hemtt_sqf::parser::run(&database, &processed).unwrap();
+    assert_eq!(parsed.content().len(), 5);
+
+    // literal arguments are checked against the declared types
+    let (warnings, _) = analyze(&parsed, Some(&project), &processed, None, &database);
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|warning| (warning.ident(), warning.message()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "SAW12",
+                "`interceptCall` expects Array on the right, not String".to_string()
+            ),
+            (
+                "SAW12",
+                "`interceptSend` expects Object on the left, not String".to_string()
+            ),
+            (
+                "SAW12",
+                "`interceptSend` expects String on the right, not Number".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn database_custom_commands_invalid() {
+    let project = ProjectConfig::from_str(
+        "name = \"Invalid\"\nprefix = \"inv\"\n\n[[sqf.commands]]\nname = \"interceptCall\"\nsyntax = \"unary\"\nleft = \"Object\"\n",
+    )
+    .unwrap();
+    assert!(project.sqf().commands()[0].validate().is_err());
+    // unknown types are rejected when project.toml is read
+    assert!(ProjectConfig::from_str(
+        "name = \"Invalid\"\nprefix = \"inv\"\n\n[[sqf.commands]]\nname = \"interceptSend\"\nsyntax = \"binary\"\nright = \"Strng\"\n",
+    )
+    .is_err());
+}
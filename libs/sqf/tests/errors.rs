@@ -47,3 +47,20 @@ fn errors(dir: &str) {
 
 errors!(spe1_invalid_token);
 errors!(spe2_unparseable);
+errors!(spe2_multiple);
+
+#[test]
+fn errors_spe2_multiple_partial() {
+    let folder = std::path::PathBuf::from(ROOT).join("spe2_multiple");
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.sqf").unwrap();
+    let processed = Processor::run(&source).unwrap();
+    let parsed = hemtt_sqf::parser::run(&Database::default(), &processed).unwrap_err();
+    assert_eq!(parsed.codes().len(), 3);
+    let partial = parsed.partial().expect("partial statements");
+    // `private _a = 1` and the `if` statement
+    assert_eq!(partial.content().len(), 2);
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use hemtt_common::project::sqf::FormatOptions;
+
+const ROOT: &str = "tests/formatter/";
+
+macro_rules! format {
+    ($dir:ident) => {
+        paste::paste! {
+            #[test]
+            fn [<format_ $dir>]() {
+                test_format(stringify!($dir));
+            }
+        }
+    };
+}
+
+fn test_format(dir: &str) {
+    let folder = PathBuf::from(ROOT).join(dir);
+    let source = std::fs::read_to_string(folder.join("source.sqf")).unwrap();
+    let options = std::fs::read_to_string(folder.join("options.toml")).map_or_else(
+        |_| FormatOptions::default(),
+        |options| toml::from_str(&options).unwrap(),
+    );
+    let formatted = hemtt_sqf::formatter::format(&source, &options).unwrap();
+    let expected = std::fs::read_to_string(folder.join("formatted.sqf")).unwrap();
+    if expected.is_empty() {
+        std::fs::write(folder.join("formatted.sqf"), &formatted).unwrap();
+    }
+    assert_eq!(formatted.replace('\r', ""), expected.replace('\r', ""));
+    assert_eq!(
+        hemtt_sqf::formatter::format(&formatted, &options).unwrap(),
+        formatted,
+        "formatting is not stable"
+    );
+}
+
+format!(basic);
+format!(macros);
+format!(next_line);
+format!(semicolons);
+
+#[test]
+fn format_crlf() {
+    let folder = PathBuf::from(ROOT).join("macros");
+    let source = std::fs::read_to_string(folder.join("source.sqf"))
+        .unwrap()
+        .replace('\r', "")
+        .replace('\n', "\r\n");
+    let expected = std::fs::read_to_string(folder.join("formatted.sqf"))
+        .unwrap()
+        .replace('\r', "")
+        .replace('\n', "\r\n");
+    let formatted = hemtt_sqf::formatter::format(&source, &FormatOptions::default()).unwrap();
+    assert_eq!(formatted, expected);
+}
@@ -0,0 +1,114 @@
+use std::{io::Write, path::PathBuf};
+
+use hemtt_preprocessor::Processor;
+use hemtt_sqf::{
+    compiler::serializer::{Constant, Instruction, SourceInfo},
+    minify::Minified,
+    parser::database::Database,
+};
+
+const ROOT: &str = "tests/minify/";
+
+macro_rules! minify {
+    ($dir:ident) => {
+        paste::paste! {
+            #[test]
+            fn [<minify_ $dir>]() {
+                test_minify(stringify!($dir));
+            }
+        }
+    };
+}
+
+fn test_minify(dir: &str) {
+    let folder = PathBuf::from(ROOT).join(dir);
+    let source = std::fs::read_to_string(folder.join("source.sqf"))
+        .unwrap()
+        .replace('\r', "");
+    let minified = hemtt_sqf::minify::minify(&source);
+    let expected = std::fs::read_to_string(folder.join("minified.sqf")).unwrap();
+    if expected.is_empty() {
+        std::fs::write(folder.join("minified.sqf"), &minified).unwrap();
+    }
+    assert_eq!(minified, expected.replace('\r', ""));
+    assert_eq!(
+        minified.lines().count(),
+        source.lines().count(),
+        "minifying changed the number of lines"
+    );
+    assert_eq!(
+        hemtt_sqf::minify::minify(&minified),
+        minified,
+        "minifying is not stable"
+    );
+}
+
+minify!(basic);
+minify!(macro_words);
+minify!(macros);
+
+#[test]
+fn minify_compiled() {
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .memory()
+        .finish(None)
+        .unwrap();
+    let path = workspace.join("source.sqf").unwrap();
+    path.create_file()
+        .unwrap()
+        .write_all(
+            b"params [\"_units\"];\n\n{\n    // heal every unit\n    _x setDamage   0;\n} forEach _units;\n\nhint str   (count _units);\n",
+        )
+        .unwrap();
+    let processed = Processor::run(&path).unwrap();
+    let sqf = hemtt_sqf::parser::run(&Database::default(), &processed).unwrap();
+    let compiled = sqf.compile(&processed).unwrap();
+    let minified = sqf.compile_minified(&processed).unwrap();
+    let source = processed.as_str().chars().collect::<Vec<_>>();
+    let text = Minified::new(processed.as_str())
+        .as_str()
+        .chars()
+        .collect::<Vec<_>>();
+    let infos = source_infos(&compiled.constants_cache);
+    let minified_infos = source_infos(&minified.constants_cache);
+    assert!(!infos.is_empty());
+    assert_eq!(infos.len(), minified_infos.len());
+    for (info, minified_info) in infos.iter().zip(&minified_infos) {
+        // script errors report the same line, and point at the same character
+        assert_eq!(info.file_line, minified_info.file_line);
+        assert_eq!(info.file_index, minified_info.file_index);
+        assert_eq!(
+            source[info.offset as usize],
+            text[minified_info.offset as usize]
+        );
+    }
+    let Constant::Code(entry) = &minified.constants_cache[minified.entry_point as usize] else {
+        panic!("the entry point is code");
+    };
+    assert_eq!(
+        minified.constants_cache[entry.source_string_index as usize],
+        Constant::String(hemtt_sqf::minify::minify(processed.as_str()))
+    );
+}
+
+/// The source info of every instruction, in order
+fn source_infos(constants: &[Constant]) -> Vec<SourceInfo> {
+    constants
+        .iter()
+        .filter_map(|constant| match constant {
+            Constant::Code(instructions) => Some(&instructions.contents),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|instruction| match instruction {
+            Instruction::CallUnary(_, info)
+            | Instruction::CallBinary(_, info)
+            | Instruction::CallNular(_, info)
+            | Instruction::AssignTo(_, info)
+            | Instruction::AssignToLocal(_, info)
+            | Instruction::GetVariable(_, info)
+            | Instruction::MakeArray(_, info) => Some(*info),
+            Instruction::EndStatement | Instruction::Push(_) => None,
+        })
+        .collect()
+}
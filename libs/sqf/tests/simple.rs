@@ -27,7 +27,7 @@ fn simple(file: &str) {
     std::fs::write(format!("tests/simple/{file}.sqfp"), processed.as_str()).unwrap();
     let parsed = match hemtt_sqf::parser::run(&Database::default(), &processed) {
         Ok(sqf) => sqf,
-        Err(hemtt_sqf::parser::ParserError::ParsingError(e)) => {
+        Err(hemtt_sqf::parser::ParserError::ParsingError(e, _)) => {
             for error in e {
                 println!(
                     "{}",